@@ -0,0 +1,290 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
+use std::panic;
+
+use crate::grid::Grid;
+use crate::{anonymize_day, input, inspect_day, try_run_part};
+
+/// A small pseudorandom number generator (SplitMix64). Anonymizing with the
+/// same seed always gives the same input, so a shared input can be made
+/// again from the seed alone.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `range`.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+        match (end - start).checked_add(1) {
+            Some(len) => start + self.next_u64() % len,
+            None => self.next_u64(),
+        }
+    }
+
+    pub fn coin(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..=items.len() as u64 - 1) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0..=i as u64) as usize);
+        }
+    }
+
+    /// Returns a random word of lowercase letters.
+    pub fn word(&mut self, len: usize) -> String {
+        (0..len)
+            .map(|_| char::from(b'a' + self.range(0..=25) as u8))
+            .collect()
+    }
+}
+
+/// Gives each label in an input a new random label, the same one every time
+/// it comes up.
+pub struct Labels {
+    labels: HashMap<String, String>,
+    used: HashSet<String>,
+}
+
+impl Labels {
+    /// Labels the puzzle gives a meaning to, like `AAA` or `in`, keep their
+    /// names.
+    pub fn new(keep: &[&str]) -> Self {
+        Self {
+            labels: keep
+                .iter()
+                .map(|&label| (label.into(), label.into()))
+                .collect(),
+            used: keep.iter().map(|&label| label.into()).collect(),
+        }
+    }
+
+    /// Returns the new label for `label`, making one with `make` the first
+    /// time `label` comes up. New labels never clash with each other or with
+    /// the labels that are kept.
+    pub fn get(
+        &mut self,
+        label: &str,
+        rng: &mut Rng,
+        make: impl Fn(&str, &mut Rng) -> String,
+    ) -> String {
+        if let Some(new_label) = self.labels.get(label) {
+            return new_label.clone();
+        }
+        let new_label = (0..1000)
+            .map(|_| make(label, rng))
+            .find(|new_label| !self.used.contains(new_label))
+            .expect("ran out of new labels");
+        self.used.insert(new_label.clone());
+        self.labels.insert(label.to_string(), new_label.clone());
+        new_label
+    }
+}
+
+/// One of the eight ways to turn or mirror a grid onto itself.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Symmetry {
+    /// Swaps rows and columns, which comes before the flips
+    pub transpose: bool,
+    /// Mirrors left and right
+    pub flip_x: bool,
+    /// Mirrors top and bottom
+    pub flip_y: bool,
+}
+
+impl Symmetry {
+    pub fn random(rng: &mut Rng) -> Self {
+        Self {
+            transpose: rng.coin(),
+            flip_x: rng.coin(),
+            flip_y: rng.coin(),
+        }
+    }
+
+    /// Rearranges a grid of characters, with `tile` giving what each tile
+    /// becomes in the new orientation.
+    pub fn apply(self, grid: &str, tile: impl Fn(char) -> char) -> String {
+        let mut grid = Grid::parse(grid, |ch| ch);
+        if self.transpose {
+            grid = grid.transpose();
+        }
+        if self.flip_x {
+            grid = grid.flip_horizontal();
+        }
+        if self.flip_y {
+            grid = grid.flip_vertical();
+        }
+        grid.rows()
+            .map(|row| row.iter().map(|&ch| tile(ch)).collect::<String>() + "\n")
+            .collect()
+    }
+}
+
+/// Rewrites a day's input into a structurally equivalent one that can be
+/// shared, then checks that the solution still parses and solves it and
+/// that none of the solution's assumptions about the input stop holding.
+/// Returns the new input with the answers to each part that solves the
+/// original input.
+pub fn anonymize(day: u8, input: &str, seed: u64) -> Result<(String, Vec<(u8, String)>), String> {
+    let input = input::normalize(input);
+    let anonymized = anonymize_day(day, &input, &mut Rng::new(seed))
+        .ok_or(format!("Day {day} can't anonymize its input"))?;
+
+    // Failures are returned as errors instead of being printed as they
+    // happen
+    let panic_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let mut answers = vec![];
+    let mut failure = None;
+    for part in [1, 2] {
+        if try_run_part(day, part, &input).is_err() {
+            continue;
+        }
+        match try_run_part(day, part, &anonymized) {
+            Ok(result) => answers.push((part, result.answer)),
+            Err(error) => {
+                failure = Some(format!(
+                    "Part {part} fails on the anonymized input: {error}"
+                ));
+                break;
+            }
+        }
+    }
+    panic::set_hook(panic_hook);
+    if let Some(failure) = failure {
+        return Err(failure);
+    }
+
+    let warnings = |input: &str| -> Vec<String> {
+        inspect_day(day, input)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|line| line.starts_with("warning:"))
+            .collect()
+    };
+    let before = warnings(&input);
+    if let Some(warning) = warnings(&anonymized)
+        .into_iter()
+        .find(|warning| !before.contains(warning))
+    {
+        return Err(format!(
+            "The anonymized input breaks an assumption: {warning}"
+        ));
+    }
+
+    Ok((anonymized, answers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DAYS;
+
+    /// Each day's examples, with the parts that can be solved from them. Day
+    /// 16's example is small enough for beams to cross every tile both ways,
+    /// which leaves nothing to redraw, so it has a bigger input as well.
+    const EXAMPLES: &[(u8, &str, &[u8])] = &[
+        (1, include_str!("fixtures/examples/day1.txt"), &[1, 2]),
+        (1, include_str!("fixtures/examples/day1-2.txt"), &[2]),
+        (2, include_str!("fixtures/examples/day2.txt"), &[1, 2]),
+        (3, include_str!("fixtures/examples/day3.txt"), &[1, 2]),
+        (4, include_str!("fixtures/examples/day4.txt"), &[1, 2]),
+        (5, include_str!("fixtures/examples/day5.txt"), &[1, 2]),
+        (6, include_str!("fixtures/examples/day6.txt"), &[1, 2]),
+        (7, include_str!("fixtures/examples/day7.txt"), &[1, 2]),
+        (8, include_str!("fixtures/examples/day8.txt"), &[1, 2]),
+        (8, include_str!("fixtures/examples/day8-3.txt"), &[2]),
+        (9, include_str!("fixtures/examples/day9.txt"), &[1, 2]),
+        (10, include_str!("fixtures/examples/day10.txt"), &[1]),
+        (10, include_str!("fixtures/examples/day10-2.txt"), &[2]),
+        (11, include_str!("fixtures/examples/day11.txt"), &[1, 2]),
+        (12, include_str!("fixtures/examples/day12.txt"), &[1, 2]),
+        (13, include_str!("fixtures/examples/day13.txt"), &[1, 2]),
+        (14, include_str!("fixtures/examples/day14.txt"), &[1, 2]),
+        (15, include_str!("fixtures/examples/day15.txt"), &[1, 2]),
+        (16, include_str!("fixtures/examples/day16.txt"), &[1, 2]),
+        (16, include_str!("fixtures/examples/day16-2.txt"), &[1, 2]),
+        (17, include_str!("fixtures/examples/day17.txt"), &[1, 2]),
+        (18, include_str!("fixtures/examples/day18.txt"), &[1, 2]),
+        (19, include_str!("fixtures/examples/day19.txt"), &[1, 2]),
+        (20, include_str!("fixtures/examples/day20.txt"), &[1]),
+        (20, include_str!("fixtures/examples/day20-2.txt"), &[1]),
+    ];
+
+    #[test]
+    fn it_works() {
+        let mut rng = Rng::new(7);
+        assert!((0..100).all(|_| (3..=5).contains(&rng.range(3..=5))));
+        let mut items = [1, 2, 3, 4, 5];
+        rng.shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!(items, [1, 2, 3, 4, 5]);
+
+        let mut labels = Labels::new(&["in"]);
+        let first = labels.get("qs", &mut rng, |_, rng| rng.word(2));
+        assert_eq!(labels.get("qs", &mut rng, |_, rng| rng.word(2)), first);
+        assert_eq!(labels.get("in", &mut rng, |_, rng| rng.word(2)), "in");
+        assert_eq!(first.len(), 2);
+
+        let symmetry = Symmetry {
+            transpose: true,
+            flip_x: true,
+            flip_y: false,
+        };
+        assert_eq!(symmetry.apply("ab\ncd\n", |ch| ch), "ca\ndb\n");
+    }
+
+    /// Each day anonymizes its examples the same way for the same seed, can
+    /// still solve what it anonymized, and changes at least one of them.
+    #[test]
+    fn days() {
+        let mut changed = HashSet::new();
+        for &(day, example, parts) in EXAMPLES {
+            if !DAYS.contains(&day) {
+                continue;
+            }
+            let input = input::normalize(example);
+            for seed in 0..8 {
+                let anonymized = anonymize_day(day, &input, &mut Rng::new(seed));
+                let anonymized =
+                    anonymized.unwrap_or_else(|| panic!("day {day} has no anonymizer"));
+                assert_eq!(
+                    anonymize_day(day, &input, &mut Rng::new(seed)),
+                    Some(anonymized.clone()),
+                    "day {day}: seed {seed} isn't repeatable"
+                );
+                if anonymized != input {
+                    changed.insert(day);
+                }
+                for &part in parts {
+                    if let Err(error) = try_run_part(day, part, &anonymized) {
+                        panic!("day {day}: part {part} fails with seed {seed}: {error}");
+                    }
+                }
+            }
+        }
+        for &(day, _, _) in EXAMPLES {
+            assert!(
+                !DAYS.contains(&day) || changed.contains(&day),
+                "day {day}: the input never changed"
+            );
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use crate::input;
+
+static PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// The file known answers are read from. This is `answers` in the input
+/// directory unless another file was set.
+pub fn path() -> PathBuf {
+    PATH.get()
+        .cloned()
+        .unwrap_or_else(|| input::dir().join("answers"))
+}
+
+/// Reads known answers from another file for the rest of the run. Only the
+/// first file set takes effect.
+pub fn set_path(path: PathBuf) {
+    let _ = PATH.set(path);
+}
+
+/// Known answers, read from an `answers` file next to the inputs. Each line
+/// holds a day, a part and the expected answer, separated by whitespace:
+///
+/// ```text
+/// 1 1 54388
+/// 1 2 53515
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers(HashMap<(u8, u8), String>);
+
+impl Answers {
+    pub fn load() -> Self {
+        match std::fs::read_to_string(path()) {
+            Ok(contents) => Self::parse(&contents),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        let answers = s
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split_ascii_whitespace();
+                let day = fields.next()?.parse().ok()?;
+                let part = fields.next()?.parse().ok()?;
+                let answer = fields.next()?;
+                Some(((day, part), answer.to_string()))
+            })
+            .collect();
+        Self(answers)
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let answers = Answers::parse("1 1 54388\n1 2 53515\n\n7 2 248029057\n");
+        assert_eq!(answers.get(1, 1), Some("54388"));
+        assert_eq!(answers.get(7, 2), Some("248029057"));
+        assert_eq!(answers.get(7, 1), None);
+    }
+}
@@ -0,0 +1,188 @@
+use std::fs;
+use std::io;
+use std::panic;
+use std::path::{Path, PathBuf};
+
+use crate::answers::Answers;
+use crate::terminal::format_table;
+use crate::try_run_part;
+
+/// The extension of the optional file next to each input that holds its
+/// expected answers, in the same format as the `answers` file.
+pub const ANSWERS_EXTENSION: &str = "answers";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Status {
+    Correct,
+    Unverified,
+    Mismatch,
+    Failed,
+}
+
+/// Runs one day against every input in a directory, printing a table of the
+/// answers and timings. An input named `alice.txt` is checked against the
+/// answers in `alice.txt.answers`, if there is one. Returns whether no input
+/// mismatched or failed.
+pub fn batch(day: u8, parts: &[u8], inputs: &Path) -> io::Result<bool> {
+    let paths = input_paths(inputs)?;
+    if paths.is_empty() {
+        println!("No inputs found in {}", inputs.display());
+        return Ok(true);
+    }
+
+    // Failures are reported in the table instead of being printed as they
+    // happen
+    let panic_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut header = vec!["Input".to_string()];
+    for part in parts {
+        header.push(format!("Part {part}"));
+        header.push("Time".to_string());
+    }
+    header.push("Status".to_string());
+
+    let mut rows = vec![header];
+    let mut statuses = vec![];
+    for path in &paths {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let input = match fs::read_to_string(path) {
+            Ok(input) => input,
+            Err(error) => {
+                rows.push(unreadable_row(&name, &error, parts.len()));
+                statuses.push(Status::Failed);
+                continue;
+            }
+        };
+        let mut row = vec![name.to_string()];
+        let answers = fs::read_to_string(answers_path(path))
+            .map(|contents| Answers::parse(&contents))
+            .unwrap_or_default();
+
+        let mut input_statuses = vec![];
+        for &part in parts {
+            let expected = answers.get(day, part);
+            match try_run_part(day, part, &input) {
+                Ok(result) => {
+                    let (answer, status) = match expected {
+                        Some(expected) if expected == result.answer => {
+                            (result.answer, Status::Correct)
+                        }
+                        Some(expected) => (
+                            format!("{} (expected {expected})", result.answer),
+                            Status::Mismatch,
+                        ),
+                        None => (result.answer, Status::Unverified),
+                    };
+                    row.push(answer);
+                    row.push(format!("{:.2?}", result.duration));
+                    input_statuses.push(status);
+                }
+                Err(error) => {
+                    row.push(error);
+                    row.push("-".to_string());
+                    input_statuses.push(Status::Failed);
+                }
+            }
+        }
+
+        let status = summarize(&input_statuses);
+        row.push(status_label(status).to_string());
+        rows.push(row);
+        statuses.push(status);
+    }
+
+    panic::set_hook(panic_hook);
+
+    print!("{}", format_table(&rows));
+
+    let count = |status| statuses.iter().filter(|&&s| s == status).count();
+    println!(
+        "\n{} inputs: {} correct, {} unverified, {} mismatched, {} failed",
+        statuses.len(),
+        count(Status::Correct),
+        count(Status::Unverified),
+        count(Status::Mismatch),
+        count(Status::Failed),
+    );
+
+    Ok(count(Status::Mismatch) == 0 && count(Status::Failed) == 0)
+}
+
+/// The row for an input that couldn't be read. The error takes the place of
+/// the first answer, and the other cells are padded so the row lines up with
+/// the header.
+fn unreadable_row(name: &str, error: &io::Error, parts: usize) -> Vec<String> {
+    let mut row = vec![name.to_string(), format!("unreadable: {error}")];
+    row.resize(2 * parts + 1, "-".to_string());
+    row.push(status_label(Status::Failed).to_string());
+    row
+}
+
+/// The answers file for an input, which adds to the input's name rather than
+/// replacing its extension, so `alice.txt` and `alice.md` don't share one.
+fn answers_path(input: &Path) -> PathBuf {
+    let mut path = input.as_os_str().to_owned();
+    path.push(".");
+    path.push(ANSWERS_EXTENSION);
+    PathBuf::from(path)
+}
+
+fn input_paths(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut paths = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        let is_answers = path
+            .extension()
+            .is_some_and(|extension| extension == ANSWERS_EXTENSION);
+        if path.is_file() && !is_hidden && !is_answers {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// The status of an input is the worst status of any of its parts.
+pub(crate) fn summarize(statuses: &[Status]) -> Status {
+    [Status::Failed, Status::Mismatch, Status::Unverified]
+        .into_iter()
+        .find(|status| statuses.contains(status))
+        .unwrap_or(Status::Correct)
+}
+
+pub(crate) fn status_label(status: Status) -> &'static str {
+    match status {
+        Status::Correct => "ok",
+        Status::Unverified => "unverified",
+        Status::Mismatch => "MISMATCH",
+        Status::Failed => "FAILED",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        use Status::*;
+        assert_eq!(summarize(&[Correct, Unverified]), Unverified);
+        assert_eq!(summarize(&[Mismatch, Failed]), Failed);
+        assert_eq!(summarize(&[Correct, Correct]), Correct);
+
+        let error = io::Error::new(io::ErrorKind::InvalidData, "not UTF-8");
+        assert_eq!(
+            unreadable_row("carol", &error, 2),
+            ["carol", "unreadable: not UTF-8", "-", "-", "-", "FAILED"]
+        );
+
+        assert_eq!(
+            answers_path(Path::new("inputs/alice.txt")),
+            Path::new("inputs/alice.txt.answers")
+        );
+    }
+}
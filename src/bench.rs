@@ -0,0 +1,140 @@
+use std::panic;
+use std::time::Duration;
+
+use crate::terminal::format_table;
+use crate::{input, json, try_run_part, Format};
+
+/// Timings from running one part several times.
+#[derive(Debug, PartialEq)]
+struct Timings {
+    min: Duration,
+    median: Duration,
+    mean: Duration,
+    max: Duration,
+}
+
+impl Timings {
+    fn new(durations: &mut [Duration]) -> Self {
+        durations.sort_unstable();
+        let total: Duration = durations.iter().sum();
+        Self {
+            min: durations[0],
+            median: durations[durations.len() / 2],
+            mean: total / durations.len() as u32,
+            max: durations[durations.len() - 1],
+        }
+    }
+}
+
+/// Runs each part of the given days `runs` times after `warmup` untimed runs,
+/// reporting how long the runs took. Answers are never cached here.
+pub fn bench(days: &[u8], parts: &[u8], runs: usize, warmup: usize, format: Format) {
+    let runs = runs.max(1);
+
+    // Failures are reported with the results instead of as they happen
+    let panic_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut results = vec![];
+    let mut errors = vec![];
+    for &day in days {
+        let Ok(input) = input::read(day) else {
+            let error = format!("No input found at {}", input::path(day).display());
+            errors.push((day, None, error));
+            continue;
+        };
+
+        'parts: for &part in parts {
+            let mut answer = String::new();
+            let mut durations = vec![];
+            for run in 0..warmup + runs {
+                match try_run_part(day, part, &input) {
+                    Ok(result) if run < warmup => answer = result.answer,
+                    Ok(result) => {
+                        answer = result.answer;
+                        durations.push(result.duration);
+                    }
+                    Err(error) => {
+                        errors.push((day, Some(part), error));
+                        continue 'parts;
+                    }
+                }
+            }
+            results.push((day, part, answer, Timings::new(&mut durations)));
+        }
+    }
+
+    panic::set_hook(panic_hook);
+
+    match format {
+        Format::Text => {
+            let mut rows = vec![["Day", "Part", "Answer", "Min", "Median", "Mean", "Max"]
+                .map(str::to_string)
+                .to_vec()];
+            for (day, part, answer, timings) in &results {
+                rows.push(vec![
+                    day.to_string(),
+                    part.to_string(),
+                    answer.clone(),
+                    format!("{:.2?}", timings.min),
+                    format!("{:.2?}", timings.median),
+                    format!("{:.2?}", timings.mean),
+                    format!("{:.2?}", timings.max),
+                ]);
+            }
+            println!("{runs} runs of each part, after {warmup} warmup runs:\n");
+            print!("{}", format_table(&rows));
+            for (day, part, error) in &errors {
+                match part {
+                    Some(part) => println!("Day {day} part {part} failed: {error}"),
+                    None => println!("Day {day}: {error}"),
+                }
+            }
+        }
+        Format::Json => {
+            let results = results.into_iter().map(|(day, part, answer, timings)| {
+                json::Value::object([
+                    ("day", day.into()),
+                    ("part", part.into()),
+                    ("answer", answer.into()),
+                    ("runs", runs.into()),
+                    ("min_ns", timings.min.as_nanos().into()),
+                    ("median_ns", timings.median.as_nanos().into()),
+                    ("mean_ns", timings.mean.as_nanos().into()),
+                    ("max_ns", timings.max.as_nanos().into()),
+                ])
+            });
+            let errors = errors.into_iter().map(|(day, part, error)| {
+                json::Value::object([
+                    ("day", day.into()),
+                    ("part", part.into()),
+                    ("error", error.into()),
+                ])
+            });
+            let output = json::Value::object([
+                ("results", json::Value::Array(results.collect())),
+                ("errors", json::Value::Array(errors.collect())),
+            ]);
+            println!("{output}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let mut durations = [5, 1, 3, 2].map(Duration::from_millis);
+        assert_eq!(
+            Timings::new(&mut durations),
+            Timings {
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(3),
+                mean: Duration::from_micros(2750),
+                max: Duration::from_millis(5),
+            }
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::{metrics, PartResult};
+
+/// Answers from earlier runs, stored in the target directory. Each answer is
+/// keyed by its day and part, the [`solution_hash`](crate::solution_hash) of
+/// the solution that gave it and a hash of the input. The whole cache is thrown away when the binary
+/// changes, since any solution may have changed with it.
+///
+/// ```text
+/// binary 5c1e0a2b9d3f4e67
+/// 7 2 9e4b07c1d2a85f36 a3f29c0d51e8b476 5811375 248029057
+/// ```
+#[derive(Debug, Default)]
+pub struct Cache {
+    binary: u64,
+    entries: HashMap<Key, (Duration, String)>,
+    changed: bool,
+}
+
+/// A day, a part, the hash of its solution and the hash of its input.
+type Key = (u8, u8, u64, u64);
+
+impl Cache {
+    pub fn path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("target/aoc-cache")
+    }
+
+    /// Loads the cache, which starts out empty if it's missing or was written
+    /// by another binary.
+    pub fn load() -> Self {
+        let binary = binary_hash();
+        let contents = fs::read_to_string(Self::path()).unwrap_or_default();
+        Self::parse(&contents, binary)
+    }
+
+    pub fn parse(s: &str, binary: u64) -> Self {
+        let mut cache = Self {
+            binary,
+            ..Self::default()
+        };
+
+        let mut lines = s.lines();
+        let written_by = lines
+            .next()
+            .and_then(|line| line.strip_prefix("binary "))
+            .and_then(|hash| u64::from_str_radix(hash, 16).ok());
+        if written_by != Some(binary) {
+            return cache;
+        }
+
+        cache.entries = lines
+            .filter_map(|line| {
+                let mut fields = line.splitn(6, ' ');
+                let day = fields.next()?.parse().ok()?;
+                let part = fields.next()?.parse().ok()?;
+                let solution = u64::from_str_radix(fields.next()?, 16).ok()?;
+                let input_hash = u64::from_str_radix(fields.next()?, 16).ok()?;
+                let duration = Duration::from_nanos(fields.next()?.parse().ok()?);
+                let answer = fields.next()?.to_string();
+                Some(((day, part, solution, input_hash), (duration, answer)))
+            })
+            .collect();
+        cache
+    }
+
+    /// Returns the cached result for a part, marked as cached. The duration
+    /// is how long the part took when it was run.
+    pub fn get(&self, day: u8, part: u8, solution: u64, input: &str) -> Option<PartResult> {
+        let (duration, answer) = self.entries.get(&(day, part, solution, fnv1a(input)))?;
+        Some(PartResult {
+            part,
+            answer: answer.clone(),
+            duration: *duration,
+            counters: metrics::Counters::new(),
+            cached: true,
+        })
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, solution: u64, input: &str, result: &PartResult) {
+        // Answers are stored one per line
+        if result.answer.contains('\n') {
+            return;
+        }
+        let key = (day, part, solution, fnv1a(input));
+        self.entries
+            .insert(key, (result.duration, result.answer.clone()));
+        self.changed = true;
+    }
+
+    /// Writes the cache back to disk, if anything was added to it.
+    pub fn save(&self) -> io::Result<()> {
+        if !self.changed {
+            return Ok(());
+        }
+        let path = Self::path();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_string())
+    }
+}
+
+impl std::fmt::Display for Cache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "binary {:016x}", self.binary)?;
+        let mut entries: Vec<_> = self.entries.iter().collect();
+        entries.sort_unstable_by_key(|(key, _)| **key);
+        for ((day, part, solution, input_hash), (duration, answer)) in entries {
+            writeln!(
+                f,
+                "{day} {part} {solution:016x} {input_hash:016x} {} {answer}",
+                duration.as_nanos()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function
+pub fn fnv1a(s: &str) -> u64 {
+    s.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Identifies the running binary by its size and modification time, which
+/// change whenever it is rebuilt.
+fn binary_hash() -> u64 {
+    let metadata = std::env::current_exe().and_then(fs::metadata);
+    let Ok(metadata) = metadata else {
+        return 0;
+    };
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .unwrap_or_default();
+    fnv1a(&format!("{} {}", metadata.len(), modified.as_nanos()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        assert_eq!(fnv1a(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a("a"), 0xaf63_dc4c_8601_ec8c);
+
+        let result = PartResult {
+            part: 2,
+            answer: "248029057".to_string(),
+            duration: Duration::from_millis(5),
+            counters: vec![("hands", 1000)],
+            cached: false,
+        };
+        let mut cache = Cache::parse("", 42);
+        cache.insert(7, 2, 1, "32T3K 765\n", &result);
+        let contents = cache.to_string();
+
+        let cache = Cache::parse(&contents, 42);
+        let cached = cache.get(7, 2, 1, "32T3K 765\n").unwrap();
+        assert_eq!(cached.answer, "248029057");
+        assert_eq!(cached.duration, Duration::from_millis(5));
+        assert!(cached.cached && cached.counters.is_empty());
+        assert!(cache.get(7, 2, 2, "32T3K 765\n").is_none());
+        assert!(cache.get(7, 2, 1, "32T3K 766\n").is_none());
+
+        // Another binary's answers are ignored
+        assert!(Cache::parse(&contents, 43)
+            .get(7, 2, 1, "32T3K 765\n")
+            .is_none());
+    }
+}
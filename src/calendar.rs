@@ -0,0 +1,123 @@
+//! Dates and times of Advent of Code puzzles, worked out without a date
+//! library.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const HOUR: i64 = 60 * 60;
+const DAY: i64 = 24 * HOUR;
+
+/// When a day's puzzle unlocks: midnight US Eastern time on that day of
+/// December.
+pub fn unlock_time(year: u16, day: u8) -> SystemTime {
+    let midnight = days_from_civil(year.into(), 12, day.into()) * DAY;
+    // Midnight is never skipped or repeated by a daylight saving change, so
+    // whichever offset is in effect an hour either side of it is the one to
+    // use.
+    let standard = midnight + 5 * HOUR;
+    let utc = if is_eastern_dst(standard) {
+        midnight + 4 * HOUR
+    } else {
+        standard
+    };
+    UNIX_EPOCH + Duration::from_secs(utc as u64)
+}
+
+/// Whether US Eastern time is on daylight saving time at a Unix time. Since
+/// 2007 that is from 2am on the second Sunday in March until 2am on the first
+/// Sunday in November, local time.
+fn is_eastern_dst(time: i64) -> bool {
+    let (year, _, _) = civil_from_days(time.div_euclid(DAY));
+    let start = nth_sunday(year, 3, 2) * DAY + 2 * HOUR + 5 * HOUR;
+    let end = nth_sunday(year, 11, 1) * DAY + 2 * HOUR + 4 * HOUR;
+    (start..end).contains(&time)
+}
+
+/// The day, counted from the Unix epoch, of the `n`th Sunday in a month.
+fn nth_sunday(year: i64, month: i64, n: i64) -> i64 {
+    let first = days_from_civil(year, month, 1);
+    // The epoch was a Thursday
+    let weekday = (first + 4).rem_euclid(7);
+    first + (7 - weekday) % 7 + 7 * (n - 1)
+}
+
+/// Days from the Unix epoch to a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    // Years start in March here, so that leap days come at the end
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The date of a day counted from the Unix epoch, as a year, month and day.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Formats a Unix time as a UTC date and time, such as `2023-12-01 05:00:00`.
+pub fn format_utc(time: u64) -> String {
+    let time = time as i64;
+    let (year, month, day) = civil_from_days(time.div_euclid(DAY));
+    let seconds = time.rem_euclid(DAY);
+    format!(
+        "{year}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+        seconds / HOUR,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Formats a duration as `1d 02:03:04`, rounding up to a whole second.
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+    match seconds / 86400 {
+        0 => time,
+        days => format!("{days}d {time}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn it_works() {
+        // 2023-12-01T05:00:00Z
+        assert_eq!(unlock_time(2023, 1), utc(1_701_406_800));
+        // 2024-12-25T05:00:00Z
+        assert_eq!(unlock_time(2024, 25), utc(1_735_102_800));
+
+        // 2023's daylight saving time ran from 2023-03-12T07:00:00Z to
+        // 2023-11-05T06:00:00Z
+        assert!(!is_eastern_dst(1_678_604_399));
+        assert!(is_eastern_dst(1_678_604_400));
+        assert!(is_eastern_dst(1_699_163_999));
+        assert!(!is_eastern_dst(1_699_164_000));
+        assert_eq!(civil_from_days(days_from_civil(2024, 2, 29)), (2024, 2, 29));
+        assert_eq!(civil_from_days(days_from_civil(2025, 1, 1)), (2025, 1, 1));
+        assert_eq!(format_utc(1_701_406_800), "2023-12-01 05:00:00");
+        assert_eq!(format_duration(Duration::from_secs(90061)), "1d 01:01:01");
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt::Display;
+use std::ops::{Add, Mul, Shl, Sub};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Checked arithmetic is on by default when built with the
+// `checked-arithmetic` feature, and can also be turned on at runtime.
+static ENABLED: AtomicBool = AtomicBool::new(cfg!(feature = "checked-arithmetic"));
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+#[inline]
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub trait Integer:
+    Copy
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Shl<u32, Output = Self>
+{
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    /// Unlike the standard `checked_shl`, this also fails when bits would be
+    /// shifted out, not only when the shift is as wide as the type.
+    fn checked_shl(self, rhs: u32) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(impl Integer for $t {
+            const ONE: Self = 1;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+            fn checked_shl(self, rhs: u32) -> Option<Self> {
+                // Shifting back has to give the original value, or bits were lost
+                <$t>::checked_shl(self, rhs).filter(|shifted| shifted >> rhs == self)
+            }
+        })*
+    };
+}
+
+impl_integer!(u32, u64, u128, usize, i64, isize);
+
+#[cold]
+fn overflow<T: Display>(a: T, operator: &str, b: impl Display) -> ! {
+    panic!(
+        "arithmetic overflow: {a} {operator} {b} does not fit in {}",
+        std::any::type_name::<T>()
+    )
+}
+
+#[inline]
+pub fn add<T: Integer>(a: T, b: T) -> T {
+    if !enabled() {
+        return a + b;
+    }
+    a.checked_add(b).unwrap_or_else(|| overflow(a, "+", b))
+}
+
+#[inline]
+pub fn sub<T: Integer>(a: T, b: T) -> T {
+    if !enabled() {
+        return a - b;
+    }
+    a.checked_sub(b).unwrap_or_else(|| overflow(a, "-", b))
+}
+
+#[inline]
+pub fn mul<T: Integer>(a: T, b: T) -> T {
+    if !enabled() {
+        return a * b;
+    }
+    a.checked_mul(b).unwrap_or_else(|| overflow(a, "*", b))
+}
+
+#[inline]
+pub fn shl<T: Integer>(a: T, bits: u32) -> T {
+    if !enabled() {
+        return a << bits;
+    }
+    a.checked_shl(bits)
+        .unwrap_or_else(|| overflow(a, "<<", bits))
+}
+
+pub fn product<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ONE, mul)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Puts the global flag back when a test is done with it, even if the
+    /// test fails.
+    struct RestoreEnabled(bool);
+
+    impl Drop for RestoreEnabled {
+        fn drop(&mut self) {
+            set_enabled(self.0);
+        }
+    }
+
+    #[test]
+    fn reports_overflow() {
+        let _restore = RestoreEnabled(enabled());
+        set_enabled(true);
+        assert_eq!(mul(3_u64, 4), 12);
+        assert_eq!(product([2_u64, 3, 7]), 42);
+        assert_eq!(shl(1_u128, 127), 1 << 127);
+
+        let result = std::panic::catch_unwind(|| mul(u64::MAX, 2));
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert_eq!(
+            message,
+            "arithmetic overflow: 18446744073709551615 * 2 does not fit in u64"
+        );
+        assert!(std::panic::catch_unwind(|| shl(1_u128, 128)).is_err());
+        assert!(std::panic::catch_unwind(|| shl(3_u64, 63)).is_err());
+        assert!(std::panic::catch_unwind(|| shl(1_i64, 63)).is_err());
+    }
+}
@@ -0,0 +1,251 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::input;
+
+/// Where Advent of Code is, unless another base URL is configured.
+pub const BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/alostsock/aoc";
+
+/// What Advent of Code said about a submitted answer.
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    /// The answer is wrong, with a hint such as "your answer is too low" when
+    /// the site gave one.
+    Incorrect(Option<String>),
+    /// Answers were submitted too recently; the message says how long to wait.
+    TooSoon(String),
+    AlreadySolved,
+    /// A response that isn't any of the above, as its text.
+    Unknown(String),
+}
+
+/// Talks to the Advent of Code site for one year's puzzles, using `curl`.
+/// Requests are made as the account whose session cookie is given, which
+/// inputs and answers are tied to. Requests to files or other sites are
+/// made without it, so they work without a session.
+pub struct Client {
+    base_url: String,
+    year: u16,
+    /// The session cookie, or why there isn't one.
+    session: Result<String, String>,
+    timeout: Option<Duration>,
+}
+
+impl Client {
+    pub fn new(year: u16, session: String, timeout: Option<Duration>) -> Self {
+        Self {
+            base_url: BASE_URL.to_string(),
+            year,
+            session: Ok(session),
+            timeout,
+        }
+    }
+
+    /// Creates a client for the configured base URL, year, session and
+    /// timeout. A missing session is only an error once a request needs it;
+    /// see [`Client::require_session`] to check for it up front.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            year: config.year,
+            session: config.session().map_err(|error| error.to_string()),
+            timeout: config.network_timeout,
+        }
+    }
+
+    /// Fails if there is no session but requests to the configured site need
+    /// one, which they don't when it's a `file://` URL.
+    pub fn require_session(self) -> io::Result<Self> {
+        if self.needs_session(&self.base_url) {
+            self.session
+                .as_ref()
+                .map_err(|error| io::Error::other(error.clone()))?;
+        }
+        Ok(self)
+    }
+
+    /// Downloads a day's input to the input directory, unless it is already
+    /// there. Returns where the input is.
+    pub fn fetch_input(&self, day: u8) -> io::Result<PathBuf> {
+        let path = input::path(day);
+        if path.exists() {
+            return Ok(path);
+        }
+
+        let input = self.get(&format!("/{}/day/{day}/input", self.year))?;
+        fs::create_dir_all(input::dir())?;
+        fs::write(&path, input)?;
+        Ok(path)
+    }
+
+    /// Downloads a day's puzzle page, which has a part's description once
+    /// it's unlocked for the session's account.
+    pub fn fetch_puzzle(&self, day: u8) -> io::Result<String> {
+        self.get(&format!("/{}/day/{day}", self.year))
+    }
+
+    /// Downloads a private leaderboard as JSON, given its id or its URL.
+    pub fn fetch_leaderboard(&self, leaderboard: &str) -> io::Result<String> {
+        if leaderboard.contains("://") {
+            self.request(leaderboard, &[])
+        } else {
+            let path = format!("/{}/leaderboard/private/view/{leaderboard}.json", self.year);
+            self.get(&path)
+        }
+    }
+
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> io::Result<Verdict> {
+        let path = format!("/{}/day/{day}/answer", self.year);
+        let form = format!("level={part}&answer={}", url_encode(answer));
+        let response = self.request(&self.url(&path), &["--data", &form])?;
+        Ok(Verdict::parse(&response))
+    }
+
+    fn get(&self, path: &str) -> io::Result<String> {
+        self.request(&self.url(path), &[])
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base_url)
+    }
+
+    /// Whether a request is made as the session's account, which it is for
+    /// Advent of Code and the configured site unless that is a `file://` URL.
+    fn needs_session(&self, url: &str) -> bool {
+        let is_under = |base: &str| {
+            url.strip_prefix(base)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        };
+        is_under(BASE_URL) || !self.base_url.starts_with("file://") && is_under(&self.base_url)
+    }
+
+    fn request(&self, url: &str, args: &[&str]) -> io::Result<String> {
+        let mut command = Command::new("curl");
+        command.args([
+            "--silent",
+            "--show-error",
+            "--fail",
+            "--user-agent",
+            USER_AGENT,
+        ]);
+        if self.needs_session(url) {
+            let session = self
+                .session
+                .as_ref()
+                .map_err(|error| io::Error::other(error.clone()))?;
+            command.arg("--cookie").arg(format!("session={session}"));
+        }
+        if let Some(timeout) = self.timeout {
+            command
+                .arg("--max-time")
+                .arg(timeout.as_secs_f64().to_string());
+        }
+        let output = command
+            .args(args)
+            .arg(url)
+            .output()
+            .map_err(|error| io::Error::other(format!("failed to run curl: {error}")))?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(io::Error::other(format!(
+                "request to {url} failed: {}",
+                error.trim()
+            )));
+        }
+        String::from_utf8(output.stdout).map_err(io::Error::other)
+    }
+}
+
+impl Verdict {
+    fn parse(response: &str) -> Self {
+        let text = main_text(response);
+        if text.contains("That's the right answer") {
+            Self::Correct
+        } else if text.contains("That's not the right answer") {
+            let hint = ["your answer is too high", "your answer is too low"]
+                .into_iter()
+                .find(|hint| text.contains(hint))
+                .map(str::to_string);
+            Self::Incorrect(hint)
+        } else if text.contains("You gave an answer too recently") {
+            Self::TooSoon(text)
+        } else if text.contains("Did you already complete it") {
+            Self::AlreadySolved
+        } else {
+            Self::Unknown(text)
+        }
+    }
+}
+
+/// Returns the text of a page's `<main>` element, or of the whole page if it
+/// has none, without any tags.
+fn main_text(html: &str) -> String {
+    let start = html.find("<main>").map_or(0, |i| i + "<main>".len());
+    let end = html[start..]
+        .find("</main>")
+        .map_or(html.len(), |i| start + i);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for ch in html[start..end].chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(ch),
+            _ => (),
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn url_encode(s: &str) -> String {
+    s.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let response = "<html><main><article><p>That's not the right answer; \
+            your answer is too low. If you're stuck, make sure you're using the \
+            full input data.</p></article></main></html>";
+        assert_eq!(
+            Verdict::parse(response),
+            Verdict::Incorrect(Some("your answer is too low".to_string()))
+        );
+        assert_eq!(
+            Verdict::parse("<main><p>That's the <em>right answer</em>!</p></main>"),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::parse("<main>\n<p>Nothing   here</p>\n</main>"),
+            Verdict::Unknown("Nothing here".to_string())
+        );
+        assert_eq!(url_encode("-12 a&b"), "-12%20a%26b");
+
+        let mut client = Client::new(2023, "cookie".to_string(), None);
+        assert!(client.needs_session("https://adventofcode.com/2023/day/1/input"));
+        assert!(!client.needs_session("https://adventofcode.com.example/1.json"));
+        assert!(!client.needs_session("file:///tmp/leaderboard.json"));
+        client.base_url = "file:///tmp/aoc".to_string();
+        assert!(!client.needs_session(&client.url("/2023/leaderboard/private/view/1.json")));
+        client.base_url = "http://localhost:8000".to_string();
+        assert!(client.needs_session(&client.url("/2023/day/1")));
+    }
+}
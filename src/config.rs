@@ -0,0 +1,315 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use clap::ValueEnum;
+
+use crate::toml::{self, Value};
+use crate::{client, vault, Format};
+
+/// The config file read from the project root, unless another one is given.
+pub const FILE_NAME: &str = "aoc.toml";
+
+/// Settings for the runner. Each setting comes from, in order of precedence:
+///
+/// 1. a command line flag
+/// 2. an environment variable
+/// 3. the config file
+/// 4. its default
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    /// `year`, `AOC_YEAR`
+    pub year: u16,
+    /// `format`, `AOC_FORMAT`
+    pub format: Format,
+    /// `paths.inputs`, `AOC_INPUT_DIR`
+    pub input_dir: PathBuf,
+    /// `paths.answers`, `AOC_ANSWERS`; defaults to `answers` in the input
+    /// directory
+    pub answers_file: Option<PathBuf>,
+    /// `paths.session`, `AOC_SESSION_FILE`: a file holding the session
+    /// cookie. `AOC_SESSION` can hold the cookie itself.
+    pub session_file: Option<PathBuf>,
+    /// `paths.encrypted`, `AOC_ENCRYPTED_DIR`: where encrypted inputs are kept
+    pub encrypted_dir: PathBuf,
+    /// `paths.key`, `AOC_KEY_FILE`: the key inputs are encrypted with
+    pub key_file: PathBuf,
+    /// `run.timeout`, `AOC_TIMEOUT`: how long each part may run, in seconds
+    pub timeout: Option<Duration>,
+    /// `run.threads`, `AOC_THREADS`: how many threads solutions split their
+    /// work over; 0 for one per core
+    pub threads: usize,
+    /// `network.timeout`, `AOC_NETWORK_TIMEOUT`: how long requests to Advent
+    /// of Code may take, in seconds
+    pub network_timeout: Option<Duration>,
+    /// `network.base_url`, `AOC_BASE_URL`: where Advent of Code is, which can
+    /// be a `file://` URL to work offline
+    pub base_url: String,
+    /// `bench.runs`, `AOC_BENCH_RUNS`
+    pub bench_runs: usize,
+    /// `bench.warmup`, `AOC_BENCH_WARMUP`: runs before the timed ones
+    pub bench_warmup: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: 2023,
+            format: Format::Text,
+            input_dir: Path::new(env!("CARGO_MANIFEST_DIR")).join("src/data"),
+            answers_file: None,
+            session_file: None,
+            encrypted_dir: vault::dir(),
+            key_file: vault::key_path(),
+            timeout: None,
+            threads: 0,
+            network_timeout: Some(Duration::from_secs(30)),
+            base_url: client::BASE_URL.to_string(),
+            bench_runs: 10,
+            bench_warmup: 1,
+        }
+    }
+}
+
+impl Config {
+    /// Loads the defaults, overridden by the config file and then by the
+    /// environment. The config file is `path` if given, then the file in
+    /// `AOC_CONFIG`, then `aoc.toml` in the project root if there is one.
+    pub fn load(path: Option<&Path>) -> Result<Self, String> {
+        let mut config = Self::default();
+
+        let env_path = std::env::var_os("AOC_CONFIG").map(PathBuf::from);
+        let default_path = Path::new(env!("CARGO_MANIFEST_DIR")).join(FILE_NAME);
+        let path = match path.map(Path::to_path_buf).or(env_path) {
+            Some(path) => Some(path),
+            None => default_path.exists().then_some(default_path),
+        };
+        if let Some(path) = path {
+            let contents = fs::read_to_string(&path)
+                .map_err(|error| format!("can't read {}: {error}", path.display()))?;
+            let base_dir = path.parent().unwrap_or(Path::new("."));
+            config
+                .apply_file(&contents, base_dir)
+                .map_err(|error| format!("{}: {error}", path.display()))?;
+        }
+
+        config.apply_env(|name| std::env::var(name).ok())?;
+        Ok(config)
+    }
+
+    /// Applies the settings in a config file. Relative paths are relative to
+    /// `base_dir`, the directory the file is in.
+    pub fn apply_file(&mut self, contents: &str, base_dir: &Path) -> Result<(), String> {
+        for (key, value) in toml::parse(contents).map_err(|error| error.to_string())? {
+            let invalid = |expected: &str| format!("`{key}` should be {expected}, not {value}");
+            let string = || match &value {
+                Value::String(s) => Ok(s.as_str()),
+                _ => Err(invalid("a string")),
+            };
+            let path = || string().map(|s| base_dir.join(expand_home(s)));
+            let count = || match value {
+                Value::Integer(n) if n >= 0 => Ok(n as usize),
+                _ => Err(invalid("a whole number")),
+            };
+            let seconds = || match value {
+                Value::Integer(n) if n >= 0 => Ok(timeout(n as f64)),
+                Value::Float(n) if n >= 0.0 => Ok(timeout(n)),
+                _ => Err(invalid("a number of seconds")),
+            };
+
+            match key.as_str() {
+                "year" => {
+                    self.year = match value {
+                        Value::Integer(n) => u16::try_from(n).map_err(|_| invalid("a year"))?,
+                        _ => return Err(invalid("a year")),
+                    }
+                }
+                "format" => self.format = parse_format(string()?)?,
+                "paths.inputs" => self.input_dir = path()?,
+                "paths.answers" => self.answers_file = Some(path()?),
+                "paths.session" => self.session_file = Some(path()?),
+                "paths.encrypted" => self.encrypted_dir = path()?,
+                "paths.key" => self.key_file = path()?,
+                "run.timeout" => self.timeout = seconds()?,
+                "run.threads" => self.threads = count()?,
+                "network.timeout" => self.network_timeout = seconds()?,
+                "network.base_url" => self.base_url = string()?.to_string(),
+                "bench.runs" => self.bench_runs = count()?,
+                "bench.warmup" => self.bench_warmup = count()?,
+                _ => return Err(format!("unknown setting `{key}`")),
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies the settings in environment variables, looked up by `var`.
+    pub fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), String> {
+        if let Some(year) = parse_var(&var, "AOC_YEAR")? {
+            self.year = year;
+        }
+        if let Some(format) = var("AOC_FORMAT") {
+            self.format = parse_format(&format)?;
+        }
+        if let Some(dir) = var("AOC_INPUT_DIR") {
+            self.input_dir = expand_home(&dir);
+        }
+        if let Some(file) = var("AOC_ANSWERS") {
+            self.answers_file = Some(expand_home(&file));
+        }
+        if let Some(file) = var("AOC_SESSION_FILE") {
+            self.session_file = Some(expand_home(&file));
+        }
+        if let Some(dir) = var("AOC_ENCRYPTED_DIR") {
+            self.encrypted_dir = expand_home(&dir);
+        }
+        if let Some(file) = var("AOC_KEY_FILE") {
+            self.key_file = expand_home(&file);
+        }
+        if let Some(seconds) = parse_var::<f64>(&var, "AOC_TIMEOUT")? {
+            self.timeout = timeout(seconds);
+        }
+        if let Some(threads) = parse_var(&var, "AOC_THREADS")? {
+            self.threads = threads;
+        }
+        if let Some(seconds) = parse_var::<f64>(&var, "AOC_NETWORK_TIMEOUT")? {
+            self.network_timeout = timeout(seconds);
+        }
+        if let Some(url) = var("AOC_BASE_URL") {
+            self.base_url = url;
+        }
+        if let Some(runs) = parse_var(&var, "AOC_BENCH_RUNS")? {
+            self.bench_runs = runs;
+        }
+        if let Some(warmup) = parse_var(&var, "AOC_BENCH_WARMUP")? {
+            self.bench_warmup = warmup;
+        }
+        Ok(())
+    }
+
+    pub fn answers_file(&self) -> PathBuf {
+        self.answers_file
+            .clone()
+            .unwrap_or_else(|| self.input_dir.join("answers"))
+    }
+
+    /// Returns the session cookie from `AOC_SESSION`, or else from the
+    /// session file.
+    pub fn session(&self) -> io::Result<String> {
+        if let Ok(session) = std::env::var("AOC_SESSION") {
+            if !session.trim().is_empty() {
+                return Ok(session.trim().to_string());
+            }
+        }
+        match &self.session_file {
+            Some(path) => fs::read_to_string(path)
+                .map(|session| session.trim().to_string())
+                .map_err(|error| {
+                    io::Error::new(
+                        error.kind(),
+                        format!("can't read the session file {}: {error}", path.display()),
+                    )
+                }),
+            None => Err(io::Error::other(
+                "set AOC_SESSION or `paths.session` in aoc.toml to your Advent of Code \
+                 session cookie",
+            )),
+        }
+    }
+}
+
+fn parse_var<T: FromStr>(
+    var: &impl Fn(&str) -> Option<String>,
+    name: &str,
+) -> Result<Option<T>, String> {
+    var(name)
+        .map(|value| {
+            value
+                .trim()
+                .parse()
+                .map_err(|_| format!("{name} should be a number, not {value:?}"))
+        })
+        .transpose()
+}
+
+fn parse_format(s: &str) -> Result<Format, String> {
+    Format::from_str(s.trim(), true).map_err(|_| format!("unknown format {s:?}"))
+}
+
+/// A timeout of zero seconds, or less, means no timeout.
+fn timeout(seconds: f64) -> Option<Duration> {
+    (seconds > 0.0).then(|| Duration::from_secs_f64(seconds))
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let mut config = Config::default();
+        config
+            .apply_file(
+                r#"
+year = 2022
+format = "json"
+
+[paths]
+inputs = "inputs"
+answers = "/answers"
+
+[run]
+timeout = 0
+threads = 2
+
+[bench]
+runs = 3
+"#,
+                Path::new("/project"),
+            )
+            .unwrap();
+        config
+            .apply_env(|name| match name {
+                "AOC_YEAR" => Some("2021".to_string()),
+                "AOC_TIMEOUT" => Some("1.5".to_string()),
+                _ => None,
+            })
+            .unwrap();
+
+        assert_eq!(
+            config,
+            Config {
+                year: 2021,
+                format: Format::Json,
+                input_dir: PathBuf::from("/project/inputs"),
+                answers_file: Some(PathBuf::from("/answers")),
+                timeout: Some(Duration::from_millis(1500)),
+                threads: 2,
+                bench_runs: 3,
+                ..Config::default()
+            }
+        );
+
+        let mut config = Config::default();
+        assert_eq!(
+            config.apply_file("[bench]\nrun = 3", Path::new(".")),
+            Err("unknown setting `bench.run`".to_string())
+        );
+        assert_eq!(
+            config.apply_file("format = 1", Path::new(".")),
+            Err("`format` should be a string, not 1".to_string())
+        );
+        assert_eq!(
+            config.apply_env(|_| Some("soon".to_string())),
+            Err("AOC_YEAR should be a number, not \"soon\"".to_string())
+        );
+    }
+}
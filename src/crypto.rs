@@ -0,0 +1,300 @@
+//! ChaCha20-Poly1305 authenticated encryption, as specified in RFC 8439.
+//! https://www.rfc-editor.org/rfc/rfc8439
+
+use std::fmt;
+
+pub const KEY_LEN: usize = 32;
+pub const NONCE_LEN: usize = 12;
+pub const TAG_LEN: usize = 16;
+
+pub type Key = [u8; KEY_LEN];
+pub type Nonce = [u8; NONCE_LEN];
+
+/// Decryption failed because the ciphertext, the associated data or the key
+/// is not the one the tag was made with.
+#[derive(Debug, PartialEq)]
+pub struct AuthenticationError;
+
+impl fmt::Display for AuthenticationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the data was tampered with, or the key is wrong")
+    }
+}
+
+impl std::error::Error for AuthenticationError {}
+
+/// Encrypts `plaintext`, returning the ciphertext followed by a tag that
+/// authenticates it along with `aad`. A nonce must never be reused with the
+/// same key.
+pub fn seal(key: &Key, nonce: &Nonce, aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+    let mut sealed = plaintext.to_vec();
+    chacha20_xor(key, 1, nonce, &mut sealed);
+    let tag = tag(key, nonce, aad, &sealed);
+    sealed.extend(tag);
+    sealed
+}
+
+/// Checks the tag at the end of `sealed` and decrypts the ciphertext before
+/// it.
+pub fn open(
+    key: &Key,
+    nonce: &Nonce,
+    aad: &[u8],
+    sealed: &[u8],
+) -> Result<Vec<u8>, AuthenticationError> {
+    let split = sealed
+        .len()
+        .checked_sub(TAG_LEN)
+        .ok_or(AuthenticationError)?;
+    let (ciphertext, expected_tag) = sealed.split_at(split);
+
+    // Compared without exiting early, so timing doesn't give away how much
+    // of the tag matched
+    let tag = tag(key, nonce, aad, ciphertext);
+    let difference = tag
+        .iter()
+        .zip(expected_tag)
+        .fold(0, |difference, (a, b)| difference | (a ^ b));
+    if difference != 0 {
+        return Err(AuthenticationError);
+    }
+
+    let mut plaintext = ciphertext.to_vec();
+    chacha20_xor(key, 1, nonce, &mut plaintext);
+    Ok(plaintext)
+}
+
+fn tag(key: &Key, nonce: &Nonce, aad: &[u8], ciphertext: &[u8]) -> [u8; TAG_LEN] {
+    let block = chacha20_block(key, 0, nonce);
+    let one_time_key: [u8; 32] = block[..32].try_into().unwrap();
+
+    let pad = |len: usize| vec![0; (16 - len % 16) % 16];
+    let mut data = aad.to_vec();
+    data.extend(pad(aad.len()));
+    data.extend(ciphertext);
+    data.extend(pad(ciphertext.len()));
+    data.extend((aad.len() as u64).to_le_bytes());
+    data.extend((ciphertext.len() as u64).to_le_bytes());
+    poly1305(&one_time_key, &data)
+}
+
+fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(12);
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(7);
+}
+
+fn chacha20_block(key: &Key, counter: u32, nonce: &Nonce) -> [u8; 64] {
+    let word = |bytes: &[u8], i: usize| u32::from_le_bytes(bytes[i * 4..][..4].try_into().unwrap());
+
+    let mut state = [0; 16];
+    state[..4].copy_from_slice(&[0x6170_7865, 0x3320_646e, 0x7962_2d32, 0x6b20_6574]);
+    for i in 0..8 {
+        state[4 + i] = word(key, i);
+    }
+    state[12] = counter;
+    for i in 0..3 {
+        state[13 + i] = word(nonce, i);
+    }
+
+    let mut working = state;
+    for _ in 0..10 {
+        quarter_round(&mut working, 0, 4, 8, 12);
+        quarter_round(&mut working, 1, 5, 9, 13);
+        quarter_round(&mut working, 2, 6, 10, 14);
+        quarter_round(&mut working, 3, 7, 11, 15);
+        quarter_round(&mut working, 0, 5, 10, 15);
+        quarter_round(&mut working, 1, 6, 11, 12);
+        quarter_round(&mut working, 2, 7, 8, 13);
+        quarter_round(&mut working, 3, 4, 9, 14);
+    }
+
+    let mut block = [0; 64];
+    for (i, (a, b)) in working.iter().zip(state).enumerate() {
+        block[i * 4..][..4].copy_from_slice(&a.wrapping_add(b).to_le_bytes());
+    }
+    block
+}
+
+fn chacha20_xor(key: &Key, counter: u32, nonce: &Nonce, data: &mut [u8]) {
+    for (i, chunk) in data.chunks_mut(64).enumerate() {
+        let block = chacha20_block(key, counter.wrapping_add(i as u32), nonce);
+        for (byte, key_byte) in chunk.iter_mut().zip(block) {
+            *byte ^= key_byte;
+        }
+    }
+}
+
+/// Computes a Poly1305 tag with numbers held in five 26-bit limbs, so that
+/// products fit in 64 bits.
+fn poly1305(key: &[u8; 32], message: &[u8]) -> [u8; TAG_LEN] {
+    const MASK: u32 = 0x3ff_ffff;
+    let word = |bytes: &[u8], i: usize| u32::from_le_bytes(bytes[i..][..4].try_into().unwrap());
+
+    // r, with the bits the algorithm requires to be clear cleared
+    let r = [
+        word(key, 0) & 0x3ff_ffff,
+        (word(key, 3) >> 2) & 0x3ff_ff03,
+        (word(key, 6) >> 4) & 0x3ff_c0ff,
+        (word(key, 9) >> 6) & 0x3f0_3fff,
+        (word(key, 12) >> 8) & 0x00f_ffff,
+    ]
+    .map(u64::from);
+    let s = [r[1] * 5, r[2] * 5, r[3] * 5, r[4] * 5];
+
+    let mut h = [0u32; 5];
+    for chunk in message.chunks(16) {
+        // Each block has a 1 appended past its last byte
+        let mut block = [0; 17];
+        block[..chunk.len()].copy_from_slice(chunk);
+        block[chunk.len()] = 1;
+
+        h[0] += word(&block, 0) & MASK;
+        h[1] += (word(&block, 3) >> 2) & MASK;
+        h[2] += (word(&block, 6) >> 4) & MASK;
+        h[3] += (word(&block, 9) >> 6) & MASK;
+        h[4] += (word(&block, 12) >> 8) | (u32::from(block[16]) << 24);
+
+        let h64 = h.map(u64::from);
+        let mut d = [
+            h64[0] * r[0] + h64[1] * s[3] + h64[2] * s[2] + h64[3] * s[1] + h64[4] * s[0],
+            h64[0] * r[1] + h64[1] * r[0] + h64[2] * s[3] + h64[3] * s[2] + h64[4] * s[1],
+            h64[0] * r[2] + h64[1] * r[1] + h64[2] * r[0] + h64[3] * s[3] + h64[4] * s[2],
+            h64[0] * r[3] + h64[1] * r[2] + h64[2] * r[1] + h64[3] * r[0] + h64[4] * s[3],
+            h64[0] * r[4] + h64[1] * r[3] + h64[2] * r[2] + h64[3] * r[1] + h64[4] * r[0],
+        ];
+        for i in 0..4 {
+            d[i + 1] += d[i] >> 26;
+            h[i] = (d[i] as u32) & MASK;
+        }
+        h[4] = (d[4] as u32) & MASK;
+        h[0] += ((d[4] >> 26) * 5) as u32;
+        h[1] += h[0] >> 26;
+        h[0] &= MASK;
+    }
+
+    // Fully carries h, then subtracts p = 2^130 - 5 if h is at least p
+    for i in 0..4 {
+        h[i + 1] += h[i] >> 26;
+        h[i] &= MASK;
+    }
+    h[0] += (h[4] >> 26) * 5;
+    h[4] &= MASK;
+    h[1] += h[0] >> 26;
+    h[0] &= MASK;
+
+    let mut g = [0u32; 5];
+    let mut carry = 5;
+    for i in 0..4 {
+        g[i] = h[i] + carry;
+        carry = g[i] >> 26;
+        g[i] &= MASK;
+    }
+    g[4] = (h[4] + carry).wrapping_sub(1 << 26);
+
+    // All ones if h + 5 - 2^130 didn't go negative, in which case it's used
+    let use_g = (g[4] >> 31).wrapping_sub(1);
+    for i in 0..5 {
+        h[i] = (h[i] & !use_g) | (g[i] & use_g);
+    }
+
+    let h = [
+        h[0] | (h[1] << 26),
+        (h[1] >> 6) | (h[2] << 20),
+        (h[2] >> 12) | (h[3] << 14),
+        (h[3] >> 18) | (h[4] << 8),
+    ];
+
+    // The tag is h + s, modulo 2^128
+    let mut tag = [0; TAG_LEN];
+    let mut carry = 0;
+    for i in 0..4 {
+        let sum = u64::from(h[i]) + u64::from(word(key, 16 + i * 4)) + carry;
+        tag[i * 4..][..4].copy_from_slice(&(sum as u32).to_le_bytes());
+        carry = sum >> 32;
+    }
+    tag
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(s: &str) -> Vec<u8> {
+        let digits: Vec<u8> = s.bytes().filter(u8::is_ascii_hexdigit).collect();
+        digits
+            .chunks(2)
+            .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap())
+            .collect()
+    }
+
+    // RFC 8439, section 2.3.2
+    #[test]
+    fn block_function() {
+        let key: Key = hex("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f")
+            .try_into()
+            .unwrap();
+        let nonce: Nonce = hex("000000090000004a00000000").try_into().unwrap();
+        let expected = hex("
+            10 f1 e7 e4 d1 3b 59 15 50 0f dd 1f a3 20 71 c4
+            c7 d1 f4 c7 33 c0 68 03 04 22 aa 9a c3 d4 6c 4e
+            d2 82 64 46 07 9f aa 09 14 c2 d7 05 d9 8b 02 a2
+            b5 12 9c d1 de 16 4e b9 cb d0 83 e8 a2 50 3c 4e");
+        assert_eq!(chacha20_block(&key, 1, &nonce).to_vec(), expected);
+    }
+
+    // RFC 8439, section 2.5.2
+    #[test]
+    fn poly1305_tag() {
+        let key: [u8; 32] = hex("85d6be7857556d337f4452fe42d506a80103808afb0db2fd4abff6af4149f51b")
+            .try_into()
+            .unwrap();
+        assert_eq!(
+            poly1305(&key, b"Cryptographic Forum Research Group").to_vec(),
+            hex("a8061dc1305136c6c22b8baf0c0127a9")
+        );
+    }
+
+    // RFC 8439, section 2.8.2
+    #[test]
+    fn aead() {
+        let plaintext = b"Ladies and Gentlemen of the class of '99: If I could offer you \
+            only one tip for the future, sunscreen would be it.";
+        let aad = hex("50515253c0c1c2c3c4c5c6c7");
+        let key: Key = hex("808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9f")
+            .try_into()
+            .unwrap();
+        let nonce: Nonce = hex("070000004041424344454647").try_into().unwrap();
+        let expected = hex("
+            d3 1a 8d 34 64 8e 60 db 7b 86 af bc 53 ef 7e c2
+            a4 ad ed 51 29 6e 08 fe a9 e2 b5 a7 36 ee 62 d6
+            3d be a4 5e 8c a9 67 12 82 fa fb 69 da 92 72 8b
+            1a 71 de 0a 9e 06 0b 29 05 d6 a5 b6 7e cd 3b 36
+            92 dd bd 7f 2d 77 8b 8c 98 03 ae e3 28 09 1b 58
+            fa b3 24 e4 fa d6 75 94 55 85 80 8b 48 31 d7 bc
+            3f f4 de f0 8e 4b 7a 9d e5 76 d2 65 86 ce c6 4b
+            61 16
+            1a e1 0b 59 4f 09 e2 6a 7e 90 2e cb d0 60 06 91");
+
+        let sealed = seal(&key, &nonce, &aad, plaintext);
+        assert_eq!(sealed, expected);
+        assert_eq!(open(&key, &nonce, &aad, &sealed), Ok(plaintext.to_vec()));
+
+        let mut tampered = sealed.clone();
+        tampered[0] ^= 1;
+        assert_eq!(
+            open(&key, &nonce, &aad, &tampered),
+            Err(AuthenticationError)
+        );
+        assert_eq!(open(&key, &nonce, b"", &sealed), Err(AuthenticationError));
+        assert_eq!(
+            open(&key, &nonce, &aad, &sealed[..10]),
+            Err(AuthenticationError)
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::anonymize::Rng;
 use crate::Solution;
 
 #[derive(Default)]
@@ -6,15 +7,40 @@ pub struct Day1 {}
 impl Solution for Day1 {
     type Result = usize;
 
-    fn part_1(&self) -> Self::Result {
-        let input = include_str!("data/day1");
+    fn part_1(&self, input: &str) -> Self::Result {
         calibration_value(input)
     }
 
-    fn part_2(&self) -> Self::Result {
-        let input = include_str!("data/day1");
+    fn part_2(&self, input: &str) -> Self::Result {
         calibration_value_lettered(input)
     }
+
+    /// Replaces every digit, spelled out or not, with a random one and every
+    /// other letter with a random letter.
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        let mut anonymized = String::new();
+        for line in input.lines() {
+            let mut rest = line;
+            while let Some(char) = rest.chars().next() {
+                let lettered_digit = LETTERED_DIGITS
+                    .iter()
+                    .find(|(_, lettered_digit)| rest.starts_with(lettered_digit));
+                if let Some((_, lettered_digit)) = lettered_digit {
+                    anonymized.push_str(rng.pick(&LETTERED_DIGITS).1);
+                    rest = &rest[lettered_digit.len()..];
+                    continue;
+                }
+                if char.is_ascii_digit() {
+                    anonymized.push_str(&rng.range(1..=9).to_string());
+                } else {
+                    anonymized.push_str(&rng.word(1));
+                }
+                rest = &rest[char.len_utf8()..];
+            }
+            anonymized.push('\n');
+        }
+        Some(anonymized)
+    }
 }
 
 fn calibration_value(input: &str) -> usize {
@@ -87,20 +113,14 @@ fn calibration_value_lettered(input: &str) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input;
 
-    #[test]
-    fn part_1() {
-        let input = "1abc2
+    const INPUT_1: &str = "1abc2
 pqr3stu8vwx
 a1b2c3d4e5f
 treb7uchet";
 
-        assert_eq!(calibration_value(input), 142);
-    }
-
-    #[test]
-    fn part_2() {
-        let input = "two1nine
+    const INPUT_2: &str = "two1nine
 eightwothree
 abcone2threexyz
 xtwone3four
@@ -108,6 +128,23 @@ xtwone3four
 zoneight234
 7pqrstsixteen";
 
-        assert_eq!(calibration_value_lettered(input), 281);
+    #[test]
+    fn part_1() {
+        assert_eq!(calibration_value(INPUT_1), 142);
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(calibration_value_lettered(INPUT_2), 281);
+    }
+
+    #[test]
+    fn messy_input() {
+        for (mess, input) in input::messy_copies(INPUT_1) {
+            assert_eq!(calibration_value(&input), 142, "{mess}");
+        }
+        for (mess, input) in input::messy_copies(INPUT_2) {
+            assert_eq!(calibration_value_lettered(&input), 281, "{mess}");
+        }
     }
 }
@@ -1,5 +1,8 @@
 use std::collections::HashSet;
 
+use crate::anonymize::{Rng, Symmetry};
+use crate::grid::{self, Grid, Position};
+use crate::visualize::{self, Palette, Picture};
 use crate::Solution;
 
 #[derive(Default)]
@@ -8,19 +11,91 @@ pub struct Day10 {}
 impl Solution for Day10 {
     type Result = usize;
 
-    fn part_1(&self) -> Self::Result {
-        let input = include_str!("data/day10");
+    fn part_1(&self, input: &str) -> Self::Result {
         Tiles::from_str(input).find_furthest_distance_in_loop()
     }
 
-    fn part_2(&self) -> Self::Result {
-        let input = include_str!("data/day10");
+    fn part_2(&self, input: &str) -> Self::Result {
         let tiles = Tiles::from_str(input);
         let loop_path = tiles
             .find_longest_loop_dfs(tiles.start_position, vec![])
             .unwrap();
         tiles.count_tiles_in_loop(loop_path)
     }
+
+    fn inspect(&self, input: &str) -> Vec<String> {
+        let tiles = Tiles::from_str(input);
+        let start_count = tiles
+            .inner
+            .iter()
+            .filter(|(_, tile)| **tile == Tile::Start)
+            .count();
+        let (i, j) = tiles.start_position;
+        let connections = tiles.connected_pipes(tiles.start_position).len();
+
+        let mut checks = vec![format!(
+            "start at row {}, column {} with {connections} connected pipes",
+            i + 1,
+            j + 1
+        )];
+        if start_count != 1 {
+            checks.push(format!("warning: expected one start, found {start_count}"));
+        }
+        if connections != 2 {
+            checks.push("warning: the start should connect to exactly two pipes".to_string());
+        }
+        checks
+    }
+
+    fn visualize(&self, part: u8, input: &str) -> Option<Picture> {
+        let tiles = Tiles::from_str(input);
+        let loop_path = tiles.find_longest_loop_dfs(tiles.start_position, vec![])?;
+
+        let palette = Palette::new(&[('.', visualize::BACKGROUND)], visualize::DIM);
+        let mut picture = Picture::from_grid(input, &palette);
+        if part == 2 {
+            picture.fill(tiles.tiles_in_loop(loop_path.clone()), visualize::GREEN);
+        }
+        picture.fill(loop_path, visualize::HIGHLIGHT);
+        picture.fill([tiles.start_position], visualize::RED);
+        Some(picture)
+    }
+
+    /// Turns or mirrors the grid, turning each pipe with it so that it still
+    /// connects to the same neighbours.
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        let symmetry = Symmetry::random(rng);
+        Some(symmetry.apply(input, |mut ch| {
+            if symmetry.transpose {
+                ch = match ch {
+                    '-' => '|',
+                    '|' => '-',
+                    'L' => '7',
+                    '7' => 'L',
+                    _ => ch,
+                };
+            }
+            if symmetry.flip_x {
+                ch = match ch {
+                    'F' => '7',
+                    '7' => 'F',
+                    'L' => 'J',
+                    'J' => 'L',
+                    _ => ch,
+                };
+            }
+            if symmetry.flip_y {
+                ch = match ch {
+                    'F' => 'L',
+                    'L' => 'F',
+                    '7' => 'J',
+                    'J' => '7',
+                    _ => ch,
+                };
+            }
+            ch
+        }))
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -60,68 +135,40 @@ impl Tile {
     }
 }
 
-type Position = (usize, usize);
-
 struct Tiles {
-    inner: Vec<Vec<Tile>>,
-    i_max: usize,
-    j_max: usize,
+    inner: Grid<Tile>,
     start_position: Position,
 }
 
 impl Tiles {
     pub fn from_str(input: &str) -> Self {
-        let mut start_position = (0, 0);
-        let inner: Vec<Vec<_>> = input
-            .lines()
-            .enumerate()
-            .map(|(i, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(|(j, ch)| {
-                        let tile = Tile::from_char(ch);
-
-                        if tile == Tile::Start {
-                            start_position = (i, j)
-                        }
-
-                        tile
-                    })
-                    .collect()
-            })
-            .collect();
+        let inner = Grid::parse(input, Tile::from_char);
+        let start_position = inner
+            .iter()
+            .filter(|(_, tile)| **tile == Tile::Start)
+            .last()
+            .map_or((0, 0), |(position, _)| position);
 
         Self {
-            i_max: inner.len() - 1,
-            j_max: inner[0].len() - 1,
             inner,
             start_position,
         }
     }
 
-    fn get(&self, (i, j): Position) -> &Tile {
-        &self.inner[i][j]
-    }
-
     fn connected_pipes(&self, position: Position) -> Vec<Position> {
-        let current_tile = self.get(position);
-        let (i, j) = position;
-        let mut paths = vec![];
-
-        if i > 0 && current_tile.is_connected_to(self.get((i - 1, j)), 0) {
-            paths.push((i - 1, j))
-        }
-        if j < self.j_max && current_tile.is_connected_to(self.get((i, j + 1)), 1) {
-            paths.push((i, j + 1))
-        }
-        if i < self.i_max && current_tile.is_connected_to(self.get((i + 1, j)), 2) {
-            paths.push((i + 1, j))
-        }
-        if j > 0 && current_tile.is_connected_to(self.get((i, j - 1)), 3) {
-            paths.push((i, j - 1))
-        }
+        let current_tile = &self.inner[position];
 
-        paths
+        // The steps go top, right, bottom, left, like a pipe's openings
+        grid::NEIGHBOURS_4
+            .into_iter()
+            .enumerate()
+            .filter_map(|(direction_index, offset)| {
+                let next_position = self.inner.step(position, offset)?;
+                current_tile
+                    .is_connected_to(&self.inner[next_position], direction_index)
+                    .then_some(next_position)
+            })
+            .collect()
     }
 
     pub fn find_furthest_distance_in_loop(&self) -> usize {
@@ -148,7 +195,7 @@ impl Tiles {
 
         let pipe_positions = self.connected_pipes(position);
 
-        if pipe_positions.len() == 0 {
+        if pipe_positions.is_empty() {
             return None;
         }
 
@@ -159,38 +206,28 @@ impl Tiles {
     }
 
     pub fn count_tiles_in_loop(&self, path: Vec<Position>) -> usize {
+        self.tiles_in_loop(path).len()
+    }
+
+    fn tiles_in_loop(&self, path: Vec<Position>) -> Vec<Position> {
         let loop_tiles: HashSet<Position> = HashSet::from_iter(path);
         let start_tile = self.determine_start_tile();
 
-        let mut count = 0;
-
-        for i in 0..=self.i_max {
-            for j in 0..=self.j_max {
-                if self.is_tile_inside_loop(&(i, j), &loop_tiles, &start_tile) {
-                    count += 1;
-                }
-            }
-        }
-
-        count
+        self.inner
+            .positions()
+            .filter(|position| self.is_tile_inside_loop(position, &loop_tiles, &start_tile))
+            .collect()
     }
 
     fn determine_start_tile(&self) -> Tile {
-        let (i, j) = self.start_position;
-
         let mut connected_directions = [false; 4];
 
-        if let Tile::Pipe([_, _, true, _]) = self.get((i - 1, j)) {
-            connected_directions[0] = true
-        }
-        if let Tile::Pipe([_, _, _, true]) = self.get((i, j + 1)) {
-            connected_directions[1] = true
-        }
-        if let Tile::Pipe([true, _, _, _]) = self.get((i + 1, j)) {
-            connected_directions[2] = true
-        }
-        if let Tile::Pipe([_, true, _, _]) = self.get((i, j - 1)) {
-            connected_directions[3] = true
+        for (direction_index, offset) in grid::NEIGHBOURS_4.into_iter().enumerate() {
+            let neighbour = self.inner.step(self.start_position, offset);
+            // The neighbour has to open back towards the start
+            if let Some(Tile::Pipe(openings)) = neighbour.map(|position| &self.inner[position]) {
+                connected_directions[direction_index] = openings[(direction_index + 2) % 4];
+            }
         }
 
         Tile::Pipe(connected_directions)
@@ -218,7 +255,7 @@ impl Tiles {
             let tile = if (i, j) == self.start_position {
                 start_tile
             } else {
-                self.get((i, j))
+                &self.inner[(i, j)]
             };
 
             use Tile::*;
@@ -246,20 +283,15 @@ impl Tiles {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input;
 
-    #[test]
-    fn part_1() {
-        let input = "..F7.
+    const INPUT_1: &str = "..F7.
 .FJ|.
 SJ.L7
 |F--J
 LJ...";
-        assert_eq!(Tiles::from_str(input).find_furthest_distance_in_loop(), 8);
-    }
 
-    #[test]
-    fn part_2() {
-        let input = "....................
+    const INPUT_2: &str = "....................
 FF7FSF7F7F7F7F7F---7
 L|LJ||||||||||||F--J
 FL-7LJLJ||||||LJL-77
@@ -271,11 +303,33 @@ L---JF-JLJ.||-FJLJJ7
 L.L7LFJ|||||FJL7||LJ
 L7JLJL-JLJLJL--JLJ.L";
 
-        let tiles = Tiles::from_str(input);
+    #[test]
+    fn part_1() {
+        assert_eq!(Tiles::from_str(INPUT_1).find_furthest_distance_in_loop(), 8);
+    }
+
+    #[test]
+    fn part_2() {
+        let tiles = Tiles::from_str(INPUT_2);
         let loop_path = tiles
             .find_longest_loop_dfs(tiles.start_position, vec![])
             .unwrap();
 
         assert_eq!(tiles.count_tiles_in_loop(loop_path), 10);
     }
+
+    #[test]
+    fn messy_input() {
+        for (mess, input) in input::messy_copies(INPUT_1) {
+            let tiles = Tiles::from_str(&input);
+            assert_eq!(tiles.find_furthest_distance_in_loop(), 8, "{mess}");
+        }
+        for (mess, input) in input::messy_copies(INPUT_2) {
+            let tiles = Tiles::from_str(&input);
+            let loop_path = tiles
+                .find_longest_loop_dfs(tiles.start_position, vec![])
+                .unwrap();
+            assert_eq!(tiles.count_tiles_in_loop(loop_path), 10, "{mess}");
+        }
+    }
 }
@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
+use crate::anonymize::{Rng, Symmetry};
 use crate::Solution;
 
 #[derive(Default)]
@@ -8,15 +9,30 @@ pub struct Day11 {}
 impl Solution for Day11 {
     type Result = usize;
 
-    fn part_1(&self) -> Self::Result {
-        let input = include_str!("data/day11");
+    fn part_1(&self, input: &str) -> Self::Result {
         Observation::from_str(input, 2).shortest_paths()
     }
 
-    fn part_2(&self) -> Self::Result {
-        let input = include_str!("data/day11");
+    fn part_2(&self, input: &str) -> Self::Result {
         Observation::from_str(input, 1_000_000).shortest_paths()
     }
+
+    /// Shuffles the rows and columns, which keeps how many are empty, then
+    /// turns or mirrors the image.
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        let mut rows: Vec<Vec<char>> = input.lines().map(|row| row.chars().collect()).collect();
+        let width = rows.first()?.len();
+        let mut columns: Vec<usize> = (0..width).collect();
+        rng.shuffle(&mut columns);
+        rng.shuffle(&mut rows);
+
+        let mut image = String::new();
+        for row in rows {
+            image.extend(columns.iter().map(|&j| row.get(j).copied().unwrap_or('.')));
+            image.push('\n');
+        }
+        Some(Symmetry::random(rng).apply(&image, |ch| ch))
+    }
 }
 
 type Position = (usize, usize);
@@ -84,10 +100,9 @@ fn manhattan_distance((i0, j0): Position, (i1, j1): Position) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input;
 
-    #[test]
-    fn it_works() {
-        let input = "...#......
+    const INPUT: &str = "...#......
 .......#..
 #.........
 ..........
@@ -98,8 +113,26 @@ mod tests {
 .......#..
 #...#.....";
 
-        assert_eq!(Observation::from_str(input, 2).shortest_paths(), 374);
-        assert_eq!(Observation::from_str(input, 10).shortest_paths(), 1030);
-        assert_eq!(Observation::from_str(input, 100).shortest_paths(), 8410);
+    #[test]
+    fn it_works() {
+        assert_eq!(Observation::from_str(INPUT, 2).shortest_paths(), 374);
+        assert_eq!(Observation::from_str(INPUT, 10).shortest_paths(), 1030);
+        assert_eq!(Observation::from_str(INPUT, 100).shortest_paths(), 8410);
+    }
+
+    #[test]
+    fn messy_input() {
+        for (mess, input) in input::messy_copies(INPUT) {
+            assert_eq!(
+                Observation::from_str(&input, 2).shortest_paths(),
+                374,
+                "{mess}"
+            );
+            assert_eq!(
+                Observation::from_str(&input, 100).shortest_paths(),
+                8410,
+                "{mess}"
+            );
+        }
     }
 }
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
-use crate::Solution;
+use crate::anonymize::Rng;
+use crate::{metrics, parallel, Solution};
 
 #[derive(Default)]
 pub struct Day12 {}
@@ -8,15 +9,36 @@ pub struct Day12 {}
 impl Solution for Day12 {
     type Result = usize;
 
-    fn part_1(&self) -> Self::Result {
-        let input = include_str!("data/day12");
+    fn part_1(&self, input: &str) -> Self::Result {
         count_arrangements(input, 1)
     }
 
-    fn part_2(&self) -> Self::Result {
-        let input = include_str!("data/day12");
+    fn part_2(&self, input: &str) -> Self::Result {
         count_arrangements(input, 5)
     }
+
+    /// Mirrors some records along with their groups, and forgets the
+    /// condition of a few springs. Forgetting only adds arrangements, so every
+    /// record can still be arranged.
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        let mut lines = vec![];
+        for line in input.lines() {
+            let (condition_str, groups_str) = line.split_once(' ')?;
+            let mut pattern: Vec<char> = condition_str
+                .chars()
+                .map(|ch| if rng.range(0..=7) == 0 { '?' } else { ch })
+                .collect();
+            let mut groups: Vec<&str> = groups_str.split(',').collect();
+            if rng.coin() {
+                pattern.reverse();
+                groups.reverse();
+            }
+            let pattern: String = pattern.into_iter().collect();
+            lines.push(format!("{pattern} {}\n", groups.join(",")));
+        }
+        rng.shuffle(&mut lines);
+        Some(lines.concat())
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -33,6 +55,15 @@ struct ConditionRecord {
     groups: Vec<usize>,
 }
 
+/// Arrangement counts by condition index, group index and damaged count.
+/// Hits are counted here rather than as they happen, to keep metrics out of
+/// the recursion.
+#[derive(Default)]
+struct Memo {
+    counts: HashMap<(usize, usize, usize), usize>,
+    hits: u64,
+}
+
 impl ConditionRecord {
     fn from_str(s: &str, copies: usize) -> Self {
         let (condition_str, groups_str) = s.split_once(' ').unwrap();
@@ -66,12 +97,16 @@ impl ConditionRecord {
 
     fn count_arrangements(
         &self,
-        cache: &mut HashMap<(usize, usize, usize), usize>,
+        cache: &mut Memo,
         condition_index: usize,
         group_index: usize,
         damaged_count: usize,
     ) -> usize {
-        if let Some(&count) = cache.get(&(condition_index, group_index, damaged_count)) {
+        if let Some(&count) = cache
+            .counts
+            .get(&(condition_index, group_index, damaged_count))
+        {
+            cache.hits += 1;
             return count;
         }
 
@@ -123,6 +158,7 @@ impl ConditionRecord {
         };
 
         cache
+            .counts
             .entry((condition_index, group_index, damaged_count))
             .or_insert(count);
 
@@ -131,18 +167,22 @@ impl ConditionRecord {
 }
 
 fn count_arrangements(input: &str, copies: usize) -> usize {
-    input
-        .lines()
-        .map(|line| {
-            let cache = &mut HashMap::default();
-            ConditionRecord::from_str(line, copies).count_arrangements(cache, 0, 0, 0)
-        })
-        .sum()
+    let lines: Vec<&str> = input.lines().collect();
+    parallel::map(&lines, |line| {
+        let cache = &mut Memo::default();
+        let count = ConditionRecord::from_str(line, copies).count_arrangements(cache, 0, 0, 0);
+        metrics::add("memo entries", cache.counts.len() as u64);
+        metrics::add("memo hits", cache.hits);
+        count
+    })
+    .into_iter()
+    .sum()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input;
 
     const INPUT: &str = "???.### 1,1,3
 .??..??...?##. 1,1,3
@@ -160,4 +200,12 @@ mod tests {
     fn part_2() {
         assert_eq!(count_arrangements(INPUT, 5), 525152);
     }
+
+    #[test]
+    fn messy_input() {
+        for (mess, input) in input::messy_copies(INPUT) {
+            assert_eq!(count_arrangements(&input, 1), 21, "{mess}");
+            assert_eq!(count_arrangements(&input, 5), 525152, "{mess}");
+        }
+    }
 }
@@ -1,4 +1,6 @@
-use crate::Solution;
+use crate::anonymize::{Rng, Symmetry};
+use crate::grid::Grid;
+use crate::{input, Solution};
 
 #[derive(Default)]
 pub struct Day13 {}
@@ -6,17 +8,84 @@ pub struct Day13 {}
 impl Solution for Day13 {
     type Result = usize;
 
-    fn part_1(&self) -> Self::Result {
-        let input = include_str!("data/day13");
+    fn part_1(&self, input: &str) -> Self::Result {
         summarize(input)
     }
 
-    fn part_2(&self) -> Self::Result {
-        let input = include_str!("data/day13");
+    fn part_2(&self, input: &str) -> Self::Result {
         summarize_smudged_variants(input)
     }
+
+    fn inspect(&self, input: &str) -> Vec<String> {
+        let sizes: Vec<(usize, usize)> = input::sections(input)
+            .map(|pattern| {
+                let width = pattern.lines().map(str::len).max().unwrap_or(0);
+                (width, pattern.lines().count())
+            })
+            .collect();
+        let max_width = sizes.iter().map(|size| size.0).max().unwrap_or(0);
+        let max_height = sizes.iter().map(|size| size.1).max().unwrap_or(0);
+
+        let mut checks = vec![format!(
+            "{} patterns, up to {max_width} wide and {max_height} tall",
+            sizes.len()
+        )];
+        // Rows and columns are stored as bitmasks, one bit per item
+        for (index, &(width, height)) in sizes.iter().enumerate() {
+            if width.max(height) > BITMASK_WIDTH {
+                checks.push(format!(
+                    "warning: pattern {} is {width} x {height}, which doesn't fit in a \
+                     {BITMASK_WIDTH}-bit mask",
+                    index + 1
+                ));
+            }
+        }
+        checks
+    }
+
+    /// Turns or mirrors each pattern on its own, which moves its line of
+    /// reflection without breaking it, and shuffles the patterns.
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        let mut patterns: Vec<String> = input::sections(input)
+            .map(|pattern_str| Symmetry::random(rng).apply(pattern_str, |ch| ch))
+            .collect();
+        rng.shuffle(&mut patterns);
+        Some(patterns.join("\n"))
+    }
+
+    fn explain(&self, part: u8, input: &str) -> Option<String> {
+        let mut lines = vec![];
+        for (index, pattern_str) in input::sections(input).enumerate() {
+            let reflection = if part == 1 {
+                find_reflection(pattern_str)
+            } else {
+                find_smudged_reflection(pattern_str)
+            };
+            let line = match reflection {
+                (Some(vertical_index), _) => format!(
+                    "vertical, between columns {} and {}",
+                    vertical_index + 1,
+                    vertical_index + 2
+                ),
+                (_, Some(horizontal_index)) => format!(
+                    "horizontal, between rows {} and {}",
+                    horizontal_index + 1,
+                    horizontal_index + 2
+                ),
+                (None, None) => "none found".to_string(),
+            };
+            lines.push(format!(
+                "Pattern {}: {line} (scores {})",
+                index + 1,
+                score(reflection)
+            ));
+        }
+        Some(lines.join("\n"))
+    }
 }
 
+const BITMASK_WIDTH: usize = usize::BITS as usize;
+
 #[derive(Debug, PartialEq, Clone)]
 enum Item {
     Ash,
@@ -40,77 +109,37 @@ struct Pattern {
 
 impl Pattern {
     fn from_str(s: &str) -> Self {
-        let inner: Vec<Vec<Item>> = s
-            .lines()
-            .map(|line| line.chars().map(Item::from_char).collect())
-            .collect();
-
-        let (rows_bits, cols_bits) = Self::create_bits(&inner);
-
-        Self {
-            rows_bits,
-            cols_bits,
-        }
+        Self::from_grid(&Grid::parse(s, Item::from_char))
     }
 
     fn smudged_variants_from_str(s: &str) -> impl Iterator<Item = Self> {
-        let inner: Vec<Vec<Item>> = s
-            .lines()
-            .map(|line| line.chars().map(Item::from_char).collect())
-            .collect();
+        let grid = Grid::parse(s, Item::from_char);
 
-        let item_count = inner.len() * inner[0].len();
-
-        (0..item_count).map(move |n| {
-            let mut flattened_inner: Vec<_> = inner.iter().cloned().flatten().collect();
-            flattened_inner[n] = match flattened_inner[n] {
+        grid.positions().map(move |position| {
+            let mut smudged_grid = grid.clone();
+            smudged_grid[position] = match smudged_grid[position] {
                 Item::Ash => Item::Rock,
                 Item::Rock => Item::Ash,
             };
-
-            let smudged_inner: Vec<Vec<Item>> = flattened_inner
-                .chunks(inner[0].len())
-                .map(|chunk| chunk.to_vec())
-                .collect();
-
-            let (smudged_rows_bits, smudged_cols_bits) = Self::create_bits(&smudged_inner);
-
-            Self {
-                rows_bits: smudged_rows_bits,
-                cols_bits: smudged_cols_bits,
-            }
+            Self::from_grid(&smudged_grid)
         })
     }
 
-    fn create_bits(inner: &Vec<Vec<Item>>) -> (Vec<usize>, Vec<usize>) {
-        let i_max = inner.len() - 1;
-        let j_max = inner[0].len() - 1;
-
-        let rows_bits = (0..=i_max)
-            .map(|i| {
-                (0..=j_max).fold(0, |bits, j| {
-                    if inner[i][j] == Item::Rock {
-                        bits | (1 << j)
-                    } else {
-                        bits
-                    }
-                })
-            })
-            .collect();
-
-        let cols_bits = (0..=j_max)
-            .map(|j| {
-                (0..=i_max).fold(0, |bits, i| {
-                    if inner[i][j] == Item::Rock {
-                        bits | (1 << i)
-                    } else {
-                        bits
-                    }
-                })
-            })
-            .collect();
+    fn from_grid(grid: &Grid<Item>) -> Self {
+        Self {
+            rows_bits: grid.rows().map(|row| Self::to_bits(row.iter())).collect(),
+            cols_bits: grid.columns().map(Self::to_bits).collect(),
+        }
+    }
 
-        (rows_bits, cols_bits)
+    fn to_bits<'a>(items: impl Iterator<Item = &'a Item>) -> usize {
+        items.enumerate().fold(0, |bits, (index, item)| {
+            if *item == Item::Rock {
+                bits | (1 << index)
+            } else {
+                bits
+            }
+        })
     }
 
     fn find_reflection(
@@ -169,36 +198,43 @@ impl Pattern {
     }
 }
 
+// The indices of the columns and rows that a vertical or horizontal line of
+// reflection comes after
+type Reflection = (Option<usize>, Option<usize>);
+
+fn find_reflection(pattern_str: &str) -> Reflection {
+    Pattern::from_str(pattern_str).find_reflection((None, None))
+}
+
+fn find_smudged_reflection(pattern_str: &str) -> Reflection {
+    let initial_result = find_reflection(pattern_str);
+
+    for smudged_pattern in Pattern::smudged_variants_from_str(pattern_str) {
+        let smudged_result = smudged_pattern.find_reflection(initial_result);
+        if smudged_result != (None, None) {
+            return smudged_result;
+        }
+    }
+
+    (None, None)
+}
+
+fn score(reflection: Reflection) -> usize {
+    match reflection {
+        (Some(vertical_index), _) => vertical_index + 1,
+        (_, Some(horizontal_index)) => (horizontal_index + 1) * 100,
+        (None, None) => 0,
+    }
+}
+
 fn summarize(input: &str) -> usize {
-    input
-        .split("\n\n")
-        .map(
-            |pattern_str| match Pattern::from_str(pattern_str).find_reflection((None, None)) {
-                (Some(vertical_index), _) => vertical_index + 1,
-                (_, Some(horizontal_index)) => (horizontal_index + 1) * 100,
-                (None, None) => 0,
-            },
-        )
-        .sum()
+    input::sections(input).map(find_reflection).map(score).sum()
 }
 
 fn summarize_smudged_variants(input: &str) -> usize {
-    input
-        .split("\n\n")
-        .map(|pattern_str| {
-            let initial_result = Pattern::from_str(pattern_str).find_reflection((None, None));
-
-            for smudged_pattern in Pattern::smudged_variants_from_str(pattern_str) {
-                let smudged_result = smudged_pattern.find_reflection(initial_result);
-                match smudged_result {
-                    (Some(vertical_index), _) => return vertical_index + 1,
-                    (_, Some(horizontal_index)) => return (horizontal_index + 1) * 100,
-                    (None, None) => continue,
-                }
-            }
-
-            0
-        })
+    input::sections(input)
+        .map(find_smudged_reflection)
+        .map(score)
         .sum()
 }
 
@@ -273,4 +309,25 @@ mod tests {
         assert_eq!(summarize_smudged_variants(INPUT_1), 400);
         assert_eq!(summarize_smudged_variants(INPUT_2), 1400);
     }
+
+    #[test]
+    fn inspect() {
+        let checks = Day13::new().inspect(INPUT_1);
+        assert_eq!(checks.len(), 1);
+
+        let wide_pattern = "#.".repeat(40);
+        let checks = Day13::new().inspect(&wide_pattern);
+        assert_eq!(
+            checks.last().unwrap(),
+            "warning: pattern 1 is 80 x 1, which doesn't fit in a 64-bit mask"
+        );
+    }
+
+    #[test]
+    fn messy_input() {
+        for (mess, input) in input::messy_copies(INPUT_1) {
+            assert_eq!(summarize(&input), 405, "{mess}");
+            assert_eq!(summarize_smudged_variants(&input), 400, "{mess}");
+        }
+    }
 }
@@ -1,6 +1,10 @@
 use std::collections::HashSet;
 
-use crate::Solution;
+use crate::anonymize::{Rng, Symmetry};
+use crate::grid::Grid;
+use crate::play::Animation;
+use crate::record::{self, Snapshot};
+use crate::{metrics, terminal, trace, Solution};
 
 #[derive(Default)]
 pub struct Day14 {}
@@ -8,48 +12,77 @@ pub struct Day14 {}
 impl Solution for Day14 {
     type Result = usize;
 
-    fn part_1(&self) -> Self::Result {
-        let input = include_str!("data/day14");
+    fn part_1(&self, input: &str) -> Self::Result {
         simulate(input)
     }
 
-    fn part_2(&self) -> Self::Result {
-        let input = include_str!("data/day14");
+    fn part_2(&self, input: &str) -> Self::Result {
         simulate_cycles(input)
     }
-}
 
-type Grid = Vec<Vec<char>>;
+    fn animate(&self, part: u8, input: &str) -> Animation {
+        let mut animation = Animation::new(&[('O', terminal::YELLOW), ('#', terminal::DIM)]);
+        let grid = parse(input);
+        animation.push("Initial state", grid.to_string());
+
+        if part == 1 {
+            let tilted_grid = tilt(&grid);
+            let load = calculate_load(&tilted_grid);
+            animation.push(
+                format!("Tilted north, load {load}"),
+                tilted_grid.to_string(),
+            );
+        } else {
+            simulate_cycles_with(input, &mut |nth_cycle, grid, load| {
+                if !animation.is_full() {
+                    animation.push(format!("Cycle {nth_cycle}, load {load}"), grid.to_string());
+                }
+            });
+        }
+        animation
+    }
+
+    /// Scatters the rounded rocks over the empty spaces, then turns or mirrors
+    /// the platform. The cube-shaped rocks only move with the platform.
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        let mut grid = parse(input);
+        let mut rocks: Vec<char> = grid
+            .iter()
+            .map(|(_, &ch)| ch)
+            .filter(|&ch| ch != '#')
+            .collect();
+        rng.shuffle(&mut rocks);
+        let mut rocks = rocks.into_iter();
+        for (_, ch) in grid.iter_mut().filter(|(_, ch)| **ch != '#') {
+            *ch = rocks.next()?;
+        }
+        Some(Symmetry::random(rng).apply(&grid.to_string(), |ch| ch))
+    }
+}
 
-fn parse(input: &str) -> Grid {
-    input.lines().map(|line| line.chars().collect()).collect()
+fn parse(input: &str) -> Grid<char> {
+    Grid::parse(input, |ch| ch)
 }
 
-fn tilt(grid: &Grid) -> Grid {
-    let total_rows = grid.len();
-    let total_cols = grid[0].len();
-
-    let mut tilted_grid: Grid = grid.clone();
-    for j in 0..total_cols {
-        for i in 0..total_rows {
-            match tilted_grid[i][j] {
-                'O' => {
-                    if let Some(row) = find_unobstructed_row(&tilted_grid, i, j) {
-                        tilted_grid[row][j] = 'O';
-                        tilted_grid[i][j] = '.'
-                    }
+fn tilt(grid: &Grid<char>) -> Grid<char> {
+    let mut tilted_grid = grid.clone();
+    for j in 0..grid.width() {
+        for i in 0..grid.height() {
+            if tilted_grid[(i, j)] == 'O' {
+                if let Some(row) = find_unobstructed_row(&tilted_grid, i, j) {
+                    tilted_grid[(row, j)] = 'O';
+                    tilted_grid[(i, j)] = '.'
                 }
-                _ => (),
             }
         }
     }
     tilted_grid
 }
 
-fn find_unobstructed_row(grid: &Grid, i: usize, j: usize) -> Option<usize> {
+fn find_unobstructed_row(grid: &Grid<char>, i: usize, j: usize) -> Option<usize> {
     let mut row = i;
     for i in (0..i).rev() {
-        if grid[i][j] != '.' {
+        if grid[(i, j)] != '.' {
             break;
         }
         row = i;
@@ -62,18 +95,13 @@ fn find_unobstructed_row(grid: &Grid, i: usize, j: usize) -> Option<usize> {
     }
 }
 
-fn calculate_load(grid: &Grid) -> usize {
-    let total_rows = grid.len();
-    let total_cols = grid[0].len();
+fn calculate_load(grid: &Grid<char>) -> usize {
+    let total_rows = grid.height();
 
     let mut total_load = 0;
 
-    for j in 0..total_cols {
-        for i in 0..total_rows {
-            if grid[i][j] == 'O' {
-                total_load += total_rows - i;
-            }
-        }
+    for (i, row) in grid.rows().enumerate() {
+        total_load += row.iter().filter(|&&ch| ch == 'O').count() * (total_rows - i);
     }
 
     total_load
@@ -81,30 +109,35 @@ fn calculate_load(grid: &Grid) -> usize {
 
 fn simulate(input: &str) -> usize {
     let grid = parse(input);
+    record::snapshot(|| Snapshot::new("Initial state", grid.to_string()));
     let tilted_grid = tilt(&grid);
-    calculate_load(&tilted_grid)
-}
-
-fn rotate_clockwise(grid: &Grid) -> Grid {
-    let mut rotated_grid = vec![vec!['.'; grid.len()]; grid[0].len()];
-
-    for i in 0..grid.len() {
-        for j in 0..grid[0].len() {
-            rotated_grid[j][grid.len() - i - 1] = grid[i][j];
-        }
-    }
-
-    rotated_grid
+    let load = calculate_load(&tilted_grid);
+    record::snapshot(|| {
+        Snapshot::new(
+            format!("Tilted north, load {load}"),
+            tilted_grid.to_string(),
+        )
+    });
+    load
 }
 
-fn cycle(grid: Grid) -> Grid {
-    [tilt, rotate_clockwise]
+fn cycle(grid: Grid<char>) -> Grid<char> {
+    [tilt, Grid::rotate_clockwise]
         .repeat(4)
         .into_iter()
         .fold(grid, |grid, f| f(&grid))
 }
 
 fn simulate_cycles(input: &str) -> usize {
+    simulate_cycles_with(input, &mut |_, _, _| {})
+}
+
+/// Simulates spin cycles until the loads repeat, calling `on_cycle` with the
+/// number of each cycle, the grid after it and its load.
+fn simulate_cycles_with<F>(input: &str, on_cycle: &mut F) -> usize
+where
+    F: FnMut(usize, &Grid<char>, usize),
+{
     let mut grid = parse(input);
     let mut seen_scores = vec![];
     let mut seen_scores_set = HashSet::new();
@@ -115,14 +148,20 @@ fn simulate_cycles(input: &str) -> usize {
     loop {
         grid = cycle(grid);
         nth_cycle += 1;
+        metrics::increment("cycles simulated");
         let score = calculate_load(&grid);
+        trace::trace!("cycle {nth_cycle} has load {score}:\n{grid}");
+        on_cycle(nth_cycle, &grid, score);
+        record::sample(|| {
+            Snapshot::new(format!("Cycle {nth_cycle}, load {score}"), grid.to_string())
+        });
 
         seen_scores.push(score);
         if seen_scores_set.insert(score) {
             continue;
         }
 
-        if cycle_scores.len() == 0 {
+        if cycle_scores.is_empty() {
             cycle_start = nth_cycle;
             cycle_scores.push(score);
             continue;
@@ -130,6 +169,10 @@ fn simulate_cycles(input: &str) -> usize {
 
         if cycle_scores.len() > 1_000 && cycle_scores[0] == score {
             // cycle found
+            trace::debug!(
+                "found a cycle of {} scores starting at cycle {cycle_start}",
+                cycle_scores.len()
+            );
             break;
         } else if seen_scores[cycle_start - 1 + cycle_scores.len()] == score {
             // the cycle continues
@@ -148,17 +191,10 @@ fn simulate_cycles(input: &str) -> usize {
     cycle_scores[position_in_cycle]
 }
 
-// fn print_grid(grid: &Grid) {
-//     println!();
-//     for row in grid {
-//         let line: String = row.iter().collect();
-//         println!("{line}");
-//     }
-// }
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input;
 
     const INPUT_1: &str = "O....#....
 O.OO#....#
@@ -180,4 +216,22 @@ O.#..O.#.#
     fn part_2() {
         assert_eq!(simulate_cycles(INPUT_1), 64);
     }
+
+    #[test]
+    fn animate() {
+        let animation = Day14::new().animate(1, INPUT_1);
+        assert_eq!(animation.frames.len(), 2);
+        assert_eq!(animation.frames[1].caption, "Tilted north, load 136");
+
+        let animation = Day14::new().animate(2, INPUT_1);
+        assert_eq!(animation.frames[1].caption, "Cycle 1, load 87");
+    }
+
+    #[test]
+    fn messy_input() {
+        for (mess, input) in input::messy_copies(INPUT_1) {
+            assert_eq!(simulate(&input), 136, "{mess}");
+            assert_eq!(simulate_cycles(&input), 64, "{mess}");
+        }
+    }
 }
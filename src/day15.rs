@@ -1,3 +1,4 @@
+use crate::anonymize::{Labels, Rng};
 use crate::Solution;
 
 #[derive(Default)]
@@ -6,15 +7,57 @@ pub struct Day15 {}
 impl Solution for Day15 {
     type Result = usize;
 
-    fn part_1(&self) -> Self::Result {
-        let input = include_str!("data/day15");
+    fn part_1(&self, input: &str) -> Self::Result {
         hash_csv(input)
     }
 
-    fn part_2(&self) -> Self::Result {
-        let input = include_str!("data/day15");
+    fn part_2(&self, input: &str) -> Self::Result {
         collect_lenses(input)
     }
+
+    fn explain(&self, part: u8, input: &str) -> Option<String> {
+        if part != 2 {
+            return None;
+        }
+        let mut lines = vec![];
+        for (b, lenses) in arrange_lenses(input).iter().enumerate() {
+            if lenses.is_empty() {
+                continue;
+            }
+            let lenses: Vec<String> = lenses
+                .iter()
+                .enumerate()
+                .map(|(s, (label, focal_length))| {
+                    let power = (b + 1) * (s + 1) * focal_length;
+                    format!("{label} {focal_length} (power {power})")
+                })
+                .collect();
+            lines.push(format!("Box {b}: {}", lenses.join(", ")));
+        }
+        Some(lines.join("\n"))
+    }
+
+    /// Gives each lens a new label of the same length and a new focal length.
+    /// The steps stay in order, so lenses are still added and removed
+    /// together.
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        let mut labels = Labels::new(&[]);
+        let mut steps = vec![];
+        for instruction in input.trim().split(',') {
+            let step = match instruction.split_once('=') {
+                Some((label, _)) => {
+                    let label = labels.get(label, rng, |label, rng| rng.word(label.len()));
+                    format!("{label}={}", rng.range(1..=9))
+                }
+                None => {
+                    let label = instruction.strip_suffix('-')?;
+                    labels.get(label, rng, |label, rng| rng.word(label.len())) + "-"
+                }
+            };
+            steps.push(step);
+        }
+        Some(steps.join(",") + "\n")
+    }
 }
 
 fn hash(s: &str) -> usize {
@@ -28,10 +71,10 @@ fn hash_csv(s: &str) -> usize {
     s.trim().split(',').map(hash).sum()
 }
 
-type Lens = (&'static str, usize);
-type Boxes = Vec<Vec<Lens>>;
+type Lens<'a> = (&'a str, usize);
+type Boxes<'a> = Vec<Vec<Lens<'a>>>;
 
-fn collect_lenses(s: &'static str) -> usize {
+fn arrange_lenses(s: &str) -> Boxes<'_> {
     let mut boxes: Boxes = vec![vec![]; 256];
 
     s.trim().split(',').for_each(|instruction| {
@@ -45,11 +88,15 @@ fn collect_lenses(s: &'static str) -> usize {
         }
     });
 
+    boxes
+}
+
+fn collect_lenses(s: &str) -> usize {
+    let boxes = arrange_lenses(s);
     let mut focusing_power = 0;
 
-    for b in 0..boxes.len() {
-        for s in 0..boxes[b].len() {
-            let focal_length = boxes[b][s].1;
+    for (b, lenses) in boxes.iter().enumerate() {
+        for (s, (_label, focal_length)) in lenses.iter().enumerate() {
             focusing_power += (b + 1) * (s + 1) * focal_length;
         }
     }
@@ -57,7 +104,7 @@ fn collect_lenses(s: &'static str) -> usize {
     focusing_power
 }
 
-fn add_lens(boxes: &mut Boxes, lens: Lens) {
+fn add_lens<'a>(boxes: &mut Boxes<'a>, lens: Lens<'a>) {
     let box_index = hash(lens.0);
     if let Some(existing_lens_index) = boxes[box_index]
         .iter()
@@ -69,7 +116,7 @@ fn add_lens(boxes: &mut Boxes, lens: Lens) {
     }
 }
 
-fn rm_lens(boxes: &mut Boxes, label_to_remove: &'static str) {
+fn rm_lens(boxes: &mut Boxes, label_to_remove: &str) {
     let box_index = hash(label_to_remove);
     if let Some(existing_lens_index) = boxes[box_index]
         .iter()
@@ -82,6 +129,7 @@ fn rm_lens(boxes: &mut Boxes, label_to_remove: &'static str) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input;
 
     const INPUT_1: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
 
@@ -94,4 +142,21 @@ mod tests {
     fn part_2() {
         assert_eq!(collect_lenses(INPUT_1), 145);
     }
+
+    #[test]
+    fn explain() {
+        assert_eq!(
+            Day15::new().explain(2, INPUT_1).unwrap(),
+            "Box 0: rn 1 (power 1), cm 2 (power 4)
+Box 3: ot 7 (power 28), ab 5 (power 40), pc 6 (power 72)"
+        );
+    }
+
+    #[test]
+    fn messy_input() {
+        for (mess, input) in input::messy_copies(INPUT_1) {
+            assert_eq!(hash_csv(&input), 1320, "{mess}");
+            assert_eq!(collect_lenses(&input), 145, "{mess}");
+        }
+    }
 }
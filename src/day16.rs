@@ -1,6 +1,11 @@
 use std::collections::HashSet;
 
-use crate::Solution;
+use crate::anonymize::Rng;
+use crate::grid::{Grid, Position};
+use crate::play::{self, Animation};
+use crate::record::{self, Snapshot};
+use crate::visualize::{self, Palette, Picture};
+use crate::{parallel, terminal, Solution};
 
 #[derive(Default)]
 pub struct Day16 {}
@@ -8,20 +13,116 @@ pub struct Day16 {}
 impl Solution for Day16 {
     type Result = usize;
 
-    fn part_1(&self) -> Self::Result {
-        let input = include_str!("data/day16");
+    fn part_1(&self, input: &str) -> Self::Result {
         simulate_beam(input)
     }
 
-    fn part_2(&self) -> Self::Result {
-        let input = include_str!("data/day16");
+    fn part_2(&self, input: &str) -> Self::Result {
         find_best_beam(input)
     }
-}
 
-type Grid = Vec<Vec<char>>;
+    fn animate(&self, part: u8, input: &str) -> Animation {
+        let mut grid = parse(input);
+        let (position, direction) = if part == 1 {
+            ((0, 0), Direction::Right)
+        } else {
+            best_starting_beam(&grid)
+        };
+
+        let mut steps = vec![];
+        beam(
+            &grid,
+            &mut HashSet::new(),
+            position,
+            direction,
+            &mut |position, direction| steps.push((position, direction)),
+        );
+
+        // Long beams are shown a few steps at a time
+        let stride = steps.len().div_ceil(play::MAX_FRAMES);
+        let mut animation = Animation::new(&[('#', terminal::YELLOW), ('>', terminal::RED)]);
+        for (index, &((i, j), direction)) in steps.iter().enumerate() {
+            let tile = grid[(i, j)];
+            if tile == '.' || tile == '#' {
+                grid[(i, j)] = '#';
+            }
+            if (index + 1) % stride != 0 && index + 1 != steps.len() {
+                continue;
+            }
+
+            // Marks the head of the beam without losing the tile under it
+            let head = grid[(i, j)];
+            grid[(i, j)] = '>';
+            animation.push(
+                format!(
+                    "Step {}: entering ({i}, {j}) going {direction:?}",
+                    index + 1
+                ),
+                grid.to_string(),
+            );
+            grid[(i, j)] = head;
+        }
+        animation
+    }
+
+    fn visualize(&self, part: u8, input: &str) -> Option<Picture> {
+        let grid = parse(input);
+        let (position, direction) = if part == 1 {
+            ((0, 0), Direction::Right)
+        } else {
+            best_starting_beam(&grid)
+        };
+
+        let palette = Palette::new(&[('.', visualize::BACKGROUND)], visualize::FOREGROUND);
+        let mut picture = Picture::from_grid(input, &palette);
+        let energized_tiles = energized_tiles(&grid, position, direction);
+        // Mirrors and splitters stay visible where the beam crosses them
+        let empty_tiles = energized_tiles
+            .into_iter()
+            .filter(|&(i, j)| grid[(i, j)] == '.');
+        picture.fill(empty_tiles, visualize::HIGHLIGHT);
+        Some(picture)
+    }
+
+    /// Redraws the tiles that no beam can tell apart: splitters that beams
+    /// only pass through end on and the empty space around them, and anything
+    /// that no beam reaches. The contraption isn't turned, since part 1's
+    /// beam always starts in the top left going right.
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        use Direction::*;
+
+        let mut grid = parse(input);
+        // Beams that meet carry on the same way, so they can share one set
+        let mut traveled_paths = HashSet::new();
+        for (position, direction) in starting_beams(&grid) {
+            beam(
+                &grid,
+                &mut traveled_paths,
+                position,
+                direction,
+                &mut |_, _| {},
+            );
+        }
 
-type Position = (usize, usize);
+        let positions: Vec<Position> = grid.positions().collect();
+        for position in positions {
+            let entered = |directions: [Direction; 2]| {
+                directions
+                    .into_iter()
+                    .any(|direction| traveled_paths.contains(&(position, direction)))
+            };
+            let tiles: &[char] = match (grid[position], entered([Left, Right]), entered([Up, Down]))
+            {
+                (_, false, false) => &['.', '.', '.', '.', '.', '.', '.', '.', '|', '-', '/', '\\'],
+                ('.' | '-', true, false) => &['.', '.', '.', '-'],
+                ('.' | '|', false, true) => &['.', '.', '.', '|'],
+                _ => continue,
+            };
+            grid[position] = *rng.pick(tiles);
+        }
+        Some(grid.to_string() + "\n")
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Direction {
@@ -31,37 +132,40 @@ enum Direction {
     Left,
 }
 
-fn parse(input: &str) -> Grid {
-    input.lines().map(|line| line.chars().collect()).collect()
+fn parse(input: &str) -> Grid<char> {
+    Grid::parse(input, |ch| ch)
 }
 
-#[rustfmt::skip]
-fn next_position(grid: &Grid, (i, j): Position, direction: Direction) -> Option<Position> {
-    let i_max = grid.len() - 1;
-    let j_max = grid[0].len() - 1;
-
-    match direction {
-        Direction::Up => if i > 0 { Some((i - 1, j)) } else { None },
-        Direction::Down => if i < i_max { Some((i + 1, j)) } else { None },
-        Direction::Left => if j > 0 { Some((i, j - 1)) } else { None },
-        Direction::Right => if j < j_max { Some((i, j + 1)) } else { None },
-    }
+fn next_position(grid: &Grid<char>, position: Position, direction: Direction) -> Option<Position> {
+    let offset = match direction {
+        Direction::Up => (-1, 0),
+        Direction::Down => (1, 0),
+        Direction::Left => (0, -1),
+        Direction::Right => (0, 1),
+    };
+    grid.step(position, offset)
 }
 
-fn beam(
-    grid: &Grid,
+/// Follows a beam through the grid, calling `on_step` whenever it enters a
+/// tile in a direction it hasn't entered it in before.
+fn beam<F>(
+    grid: &Grid<char>,
     traveled_paths: &mut HashSet<(Position, Direction)>,
     position: Position,
     direction: Direction,
-) {
+    on_step: &mut F,
+) where
+    F: FnMut(Position, Direction),
+{
     use Direction::*;
 
     let inserted = traveled_paths.insert((position, direction));
     if !inserted {
         return;
     }
+    on_step(position, direction);
 
-    let current_tile = grid[position.0][position.1];
+    let current_tile = grid[position];
     let next_directions = match (current_tile, direction) {
         ('/', Up) => vec![Right],
         ('/', Right) => vec![Up],
@@ -81,28 +185,74 @@ fn beam(
             continue;
         };
 
-        beam(grid, traveled_paths, position, direction);
+        beam(grid, traveled_paths, position, direction, on_step);
     }
 }
 
-fn simulate_beam(input: &str) -> usize {
-    let grid = parse(input);
+fn energized_tiles(
+    grid: &Grid<char>,
+    position: Position,
+    direction: Direction,
+) -> HashSet<Position> {
     let mut traveled_paths = HashSet::new();
-    beam(&grid, &mut traveled_paths, (0, 0), Direction::Right);
+    beam(
+        grid,
+        &mut traveled_paths,
+        position,
+        direction,
+        &mut |_, _| {},
+    );
 
-    let energized_tiles: HashSet<Position> = HashSet::from_iter(
+    HashSet::from_iter(
         traveled_paths
             .into_iter()
             .map(|(position, _direction)| position),
-    );
-    energized_tiles.len()
+    )
 }
 
-fn starting_beams(grid: &Grid) -> Vec<(Position, Direction)> {
+/// Records the steps of a beam as it energizes tiles.
+fn record_beam(grid: &Grid<char>, position: Position, direction: Direction) {
+    if !record::is_recording() {
+        return;
+    }
+
+    let mut energized_tiles = vec![];
+    let grid_str = grid.to_string();
+    let mut step = 0;
+    let on_step = &mut |position, direction| {
+        step += 1;
+        if !energized_tiles.contains(&position) {
+            energized_tiles.push(position);
+        }
+        record::sample(|| {
+            Snapshot::new(
+                format!("Step {step}: entering {position:?} going {direction:?}"),
+                grid_str.as_str(),
+            )
+            .highlight(energized_tiles.iter().copied())
+        });
+    };
+    beam(grid, &mut HashSet::new(), position, direction, on_step);
+    record::snapshot(|| {
+        Snapshot::new(
+            format!("{} tiles energized", energized_tiles.len()),
+            grid_str.as_str(),
+        )
+        .highlight(energized_tiles.iter().copied())
+    });
+}
+
+fn simulate_beam(input: &str) -> usize {
+    let grid = parse(input);
+    record_beam(&grid, (0, 0), Direction::Right);
+    energized_tiles(&grid, (0, 0), Direction::Right).len()
+}
+
+fn starting_beams(grid: &Grid<char>) -> Vec<(Position, Direction)> {
     use Direction::*;
 
-    let i_max = grid.len() - 1;
-    let j_max = grid[0].len() - 1;
+    let i_max = grid.height() - 1;
+    let j_max = grid.width() - 1;
 
     let mut beams = vec![];
     beams.extend((0..=i_max).map(|i| ((i, 0), Right)));
@@ -112,29 +262,32 @@ fn starting_beams(grid: &Grid) -> Vec<(Position, Direction)> {
     beams
 }
 
-fn find_best_beam(input: &str) -> usize {
-    let grid = parse(input);
-
-    starting_beams(&grid)
+fn best_starting_beam(grid: &Grid<char>) -> (Position, Direction) {
+    starting_beams(grid)
         .into_iter()
-        .map(|(position, direction)| {
-            let mut traveled_paths = HashSet::new();
-            beam(&grid, &mut traveled_paths, position, direction);
-
-            let energized_tiles: HashSet<Position> = HashSet::from_iter(
-                traveled_paths
-                    .into_iter()
-                    .map(|(position, _direction)| position),
-            );
-            energized_tiles.len()
-        })
-        .max()
+        .max_by_key(|&(position, direction)| energized_tiles(grid, position, direction).len())
         .unwrap()
 }
 
+fn find_best_beam(input: &str) -> usize {
+    let grid = parse(input);
+    if record::is_recording() {
+        let (position, direction) = best_starting_beam(&grid);
+        record_beam(&grid, position, direction);
+    }
+    let beams = starting_beams(&grid);
+    parallel::map(&beams, |&(position, direction)| {
+        energized_tiles(&grid, position, direction).len()
+    })
+    .into_iter()
+    .max()
+    .unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input;
 
     const INPUT_1: &str = r".|...\....
 |.-.\.....
@@ -156,4 +309,38 @@ mod tests {
     fn part_2() {
         assert_eq!(find_best_beam(INPUT_1), 51);
     }
+
+    #[test]
+    fn messy_input() {
+        for (mess, input) in input::messy_copies(INPUT_1) {
+            assert_eq!(simulate_beam(&input), 46, "{mess}");
+            assert_eq!(find_best_beam(&input), 51, "{mess}");
+        }
+    }
+
+    #[test]
+    fn anonymize_keeps_answers() {
+        // Mostly empty space with mirrors and splitters scattered through it,
+        // like a real input
+        let mut rng = Rng::new(16);
+        let input: String = (0..40)
+            .map(|_| {
+                let row: String = (0..40)
+                    .map(|_| {
+                        *rng.pick(&[
+                            '.', '.', '.', '.', '.', '.', '.', '.', '.', '|', '-', '/', '\\',
+                        ])
+                    })
+                    .collect();
+                row + "\n"
+            })
+            .collect();
+
+        for seed in 0..4 {
+            let anonymized = Day16::new().anonymize(&input, &mut Rng::new(seed)).unwrap();
+            assert_ne!(anonymized, input);
+            assert_eq!(simulate_beam(&anonymized), simulate_beam(&input));
+            assert_eq!(find_best_beam(&anonymized), find_best_beam(&input));
+        }
+    }
 }
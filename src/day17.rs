@@ -1,6 +1,10 @@
 use std::collections::{BinaryHeap, HashMap};
 
-use crate::Solution;
+use crate::anonymize::{Rng, Symmetry};
+use crate::grid::{Grid, Position};
+use crate::record::{self, Snapshot};
+use crate::visualize::{self, Palette, Picture};
+use crate::{metrics, Solution};
 
 #[derive(Default)]
 pub struct Day17 {}
@@ -8,20 +12,44 @@ pub struct Day17 {}
 impl Solution for Day17 {
     type Result = usize;
 
-    fn part_1(&self) -> Self::Result {
-        let input = include_str!("data/day17");
+    fn part_1(&self, input: &str) -> Self::Result {
         find_best_path(input, false)
     }
 
-    fn part_2(&self) -> Self::Result {
-        let input = include_str!("data/day17");
+    fn part_2(&self, input: &str) -> Self::Result {
         find_best_path(input, true)
     }
-}
 
-type Grid = Vec<Vec<char>>;
+    fn visualize(&self, part: u8, input: &str) -> Option<Picture> {
+        let (_, path) = search(&parse(input), part == 2);
+        let palette = Palette::new(&[], visualize::DIM);
+        let mut picture = Picture::from_grid(input, &palette);
+        picture.fill(path, visualize::HIGHLIGHT);
+        Some(picture)
+    }
 
-type Position = (usize, usize);
+    /// Nudges each block's heat loss up or down by one, then turns or mirrors
+    /// the map in a way that keeps the start and the factory in opposite
+    /// corners.
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        let mut map = String::new();
+        for line in input.lines() {
+            for ch in line.chars() {
+                let heat_loss = ch.to_digit(10)? + rng.range(0..=2) as u32;
+                let heat_loss = heat_loss.saturating_sub(1).clamp(1, 9);
+                map.push(char::from_digit(heat_loss, 10)?);
+            }
+            map.push('\n');
+        }
+        let half_turn = rng.coin();
+        let symmetry = Symmetry {
+            transpose: rng.coin(),
+            flip_x: half_turn,
+            flip_y: half_turn,
+        };
+        Some(symmetry.apply(&map, |ch| ch))
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Direction {
@@ -36,6 +64,8 @@ type State = (Position, Direction, usize);
 struct StatePriority {
     state: State,
     heat_loss: usize,
+    // The state this one was reached from, for recovering the path
+    previous: Option<State>,
 }
 
 impl Ord for StatePriority {
@@ -46,7 +76,7 @@ impl Ord for StatePriority {
 
 impl PartialOrd for StatePriority {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(other.heat_loss.cmp(&self.heat_loss))
+        Some(self.cmp(other))
     }
 }
 
@@ -58,44 +88,60 @@ impl PartialEq for StatePriority {
 
 impl Eq for StatePriority {}
 
-fn parse(input: &str) -> Grid {
-    input.lines().map(|line| line.chars().collect()).collect()
+fn parse(input: &str) -> Grid<char> {
+    Grid::parse(input, |ch| ch)
 }
 
-#[rustfmt::skip]
-fn next_position(grid: &Grid, (i, j): Position, direction: Direction) -> Option<Position> {
-    let i_max = grid.len() - 1;
-    let j_max = grid[0].len() - 1;
-
-    match direction {
-        Direction::Up => if i > 0 { Some((i - 1, j)) } else { None },
-        Direction::Down => if i < i_max { Some((i + 1, j)) } else { None },
-        Direction::Left => if j > 0 { Some((i, j - 1)) } else { None },
-        Direction::Right => if j < j_max { Some((i, j + 1)) } else { None },
-    }
+fn next_position(grid: &Grid<char>, position: Position, direction: Direction) -> Option<Position> {
+    let offset = match direction {
+        Direction::Up => (-1, 0),
+        Direction::Down => (1, 0),
+        Direction::Left => (0, -1),
+        Direction::Right => (0, 1),
+    };
+    grid.step(position, offset)
 }
 
-fn end_position(grid: &Grid) -> Position {
-    (grid.len() - 1, grid[0].len() - 1)
+fn end_position(grid: &Grid<char>) -> Position {
+    (grid.height() - 1, grid.width() - 1)
 }
 
 fn find_best_path(input: &str, use_slow_steering: bool) -> usize {
-    use Direction::*;
+    search(&parse(input), use_slow_steering).0
+}
 
-    let grid = parse(input);
+/// Returns the least heat loss on the way to the end, along with the
+/// positions on the path that incurs it.
+fn search(grid: &Grid<char>, use_slow_steering: bool) -> (usize, Vec<Position>) {
+    use Direction::*;
 
     let mut visited_states: HashMap<State, usize> = HashMap::new();
+    let mut came_from: HashMap<State, State> = HashMap::new();
     let mut visit_queue: BinaryHeap<StatePriority> = BinaryHeap::new();
 
     visit_queue.push(StatePriority {
         state: ((0, 0), Right, 0),
         heat_loss: 0,
+        previous: None,
     });
 
-    loop {
-        let Some(StatePriority { state, heat_loss }) = visit_queue.pop() else {
-            break;
-        };
+    let mut nodes_popped = 0;
+
+    while let Some(StatePriority {
+        state,
+        heat_loss,
+        previous,
+    }) = visit_queue.pop()
+    {
+        nodes_popped += 1;
+        record::sample(|| {
+            let frontier = visit_queue.iter().map(|priority| priority.state.0);
+            Snapshot::new(
+                format!("Popped {nodes_popped} states, heat loss {heat_loss} so far"),
+                grid.to_string(),
+            )
+            .highlight(frontier)
+        });
 
         let (position, direction, consecutive_moves) = state;
 
@@ -109,8 +155,27 @@ fn find_best_path(input: &str, use_slow_steering: bool) -> usize {
             }
         }
 
-        if position == end_position(&grid) {
-            return heat_loss;
+        if position == end_position(grid) {
+            metrics::add("nodes popped", nodes_popped);
+            metrics::add("states visited", visited_states.len() as u64);
+
+            let mut path = vec![position];
+            let mut current = previous;
+            while let Some(state) = current {
+                path.push(state.0);
+                current = came_from.get(&state).copied();
+            }
+            path.reverse();
+
+            record::snapshot(|| {
+                Snapshot::new(
+                    format!("Best path, heat loss {heat_loss}"),
+                    grid.to_string(),
+                )
+                .highlight(path.iter().copied())
+            });
+
+            return (heat_loss, path);
         }
 
         if let Some(&min_heat_loss_at_position) = visited_states.get(&state) {
@@ -120,6 +185,9 @@ fn find_best_path(input: &str, use_slow_steering: bool) -> usize {
         }
 
         visited_states.insert(state, heat_loss);
+        if let Some(previous) = previous {
+            came_from.insert(state, previous);
+        }
 
         for next_direction in [Up, Right, Left, Down] {
             // Skip backwards directions
@@ -128,7 +196,7 @@ fn find_best_path(input: &str, use_slow_steering: bool) -> usize {
                 _ => (),
             };
 
-            let Some(next_position) = next_position(&grid, position, next_direction) else {
+            let Some(next_position) = next_position(grid, position, next_direction) else {
                 continue;
             };
 
@@ -142,12 +210,12 @@ fn find_best_path(input: &str, use_slow_steering: bool) -> usize {
                 1
             };
 
-            let heat_loss =
-                heat_loss + grid[next_position.0][next_position.1].to_digit(10).unwrap() as usize;
+            let heat_loss = heat_loss + grid[next_position].to_digit(10).unwrap() as usize;
 
             visit_queue.push(StatePriority {
                 state: (next_position, next_direction, consecutive_moves),
                 heat_loss,
+                previous: Some(state),
             })
         }
     }
@@ -158,6 +226,7 @@ fn find_best_path(input: &str, use_slow_steering: bool) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input;
 
     const INPUT_1: &str = "2413432311323
 3215453535623
@@ -182,4 +251,27 @@ mod tests {
     fn part_2() {
         assert_eq!(find_best_path(INPUT_1, true), 94);
     }
+
+    #[test]
+    fn recovers_path() {
+        let grid = parse(INPUT_1);
+        for use_slow_steering in [false, true] {
+            let (heat_loss, path) = search(&grid, use_slow_steering);
+            assert_eq!(path.first(), Some(&(0, 0)));
+            assert_eq!(path.last(), Some(&end_position(&grid)));
+            let path_heat_loss: usize = path[1..]
+                .iter()
+                .map(|&(i, j)| grid[(i, j)].to_digit(10).unwrap() as usize)
+                .sum();
+            assert_eq!(path_heat_loss, heat_loss);
+        }
+    }
+
+    #[test]
+    fn messy_input() {
+        for (mess, input) in input::messy_copies(INPUT_1) {
+            assert_eq!(find_best_path(&input, false), 102, "{mess}");
+            assert_eq!(find_best_path(&input, true), 94, "{mess}");
+        }
+    }
 }
@@ -1,4 +1,6 @@
-use crate::Solution;
+use crate::anonymize::Rng;
+use crate::visualize::{self, Picture};
+use crate::{checked, Solution};
 
 #[derive(Default)]
 pub struct Day18 {}
@@ -6,15 +8,54 @@ pub struct Day18 {}
 impl Solution for Day18 {
     type Result = isize;
 
-    fn part_1(&self) -> Self::Result {
-        let input = include_str!("data/day18");
+    fn part_1(&self, input: &str) -> Self::Result {
         area(input, false)
     }
 
-    fn part_2(&self) -> Self::Result {
-        let input = include_str!("data/day18");
+    fn part_2(&self, input: &str) -> Self::Result {
         area(input, true)
     }
+
+    fn visualize(&self, part: u8, input: &str) -> Option<Picture> {
+        let ((width, height), points) = visualize::fit(&vertices(input, part == 2), 400);
+        let mut picture = Picture::new(width, height, visualize::BACKGROUND);
+        picture.outline(points, visualize::HIGHLIGHT);
+        Some(picture)
+    }
+
+    /// Turns or mirrors the dig plan, turning both the directions and the
+    /// ones hidden in the colors the same way, and starts it from a different
+    /// corner. The trench still closes up on itself.
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        const DIRECTIONS: [&str; 4] = ["R", "D", "L", "U"];
+        let turn = rng.range(0..=3) as usize;
+        let mirror = rng.coin();
+        let new_direction = |index: usize| {
+            if mirror {
+                (turn + 4 - index) % 4
+            } else {
+                (turn + index) % 4
+            }
+        };
+
+        let mut lines = vec![];
+        for line in input.lines() {
+            let (direction, rest) = line.split_once(' ')?;
+            let (distance, color) = rest.split_once(' ')?;
+            let direction = DIRECTIONS.iter().position(|&d| d == direction)?;
+            let color_direction = color.get(color.len() - 2..color.len() - 1)?;
+            let color_direction: usize = color_direction.parse().ok().filter(|&d| d < 4)?;
+            lines.push(format!(
+                "{} {distance} {}{})",
+                DIRECTIONS[new_direction(direction)],
+                &color[..color.len() - 2],
+                new_direction(color_direction)
+            ));
+        }
+        let start = rng.range(0..=lines.len().max(1) as u64 - 1) as usize;
+        lines.rotate_left(start);
+        Some(lines.join("\n") + "\n")
+    }
 }
 
 type Position = (isize, isize);
@@ -59,7 +100,7 @@ fn perimeter(input: &str, use_color: bool) -> isize {
     perimeter
 }
 
-fn inner_area(input: &str, use_color: bool) -> isize {
+fn vertices(input: &str, use_color: bool) -> Vec<Position> {
     let mut position = (0, 0);
     let mut points: Vec<Position> = vec![(0, 0)];
     for line in input.lines() {
@@ -67,13 +108,18 @@ fn inner_area(input: &str, use_color: bool) -> isize {
         position = next_position;
         points.push(position);
     }
+    points
+}
+
+fn inner_area(input: &str, use_color: bool) -> isize {
+    let points = vertices(input, use_color);
 
     // https://en.wikipedia.org/wiki/Shoelace_formula#Shoelace_formula
     let mut a = 0;
     for i in 1..points.len() {
         let (x0, y0) = points[i - 1];
         let (x1, y1) = points[i];
-        a += x0 * y1 - y0 * x1
+        a = checked::add(a, checked::sub(checked::mul(x0, y1), checked::mul(y0, x1)));
     }
     a.abs() / 2
 }
@@ -99,6 +145,7 @@ fn area(input: &str, use_color: bool) -> isize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input;
 
     const INPUT_1: &str = "R 6 (#70c710)
 D 5 (#0dc571)
@@ -120,4 +167,12 @@ U 2 (#7a21e3)";
         assert_eq!(area(INPUT_1, false), 62);
         assert_eq!(area(INPUT_1, true), 952408144115);
     }
+
+    #[test]
+    fn messy_input() {
+        for (mess, input) in input::messy_copies(INPUT_1) {
+            assert_eq!(area(&input, false), 62, "{mess}");
+            assert_eq!(area(&input, true), 952408144115, "{mess}");
+        }
+    }
 }
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
-use crate::Solution;
+use crate::anonymize::{Labels, Rng};
+use crate::dot::Graph;
+use crate::{checked, input, Solution};
 
 #[derive(Default)]
 pub struct Day19 {}
@@ -8,15 +10,99 @@ pub struct Day19 {}
 impl Solution for Day19 {
     type Result = usize;
 
-    fn part_1(&self) -> Self::Result {
-        let input = include_str!("data/day19");
+    fn part_1(&self, input: &str) -> Self::Result {
         accepted_parts_rating_sum(input)
     }
 
-    fn part_2(&self) -> Self::Result {
-        let input = include_str!("data/day19");
+    fn part_2(&self, input: &str) -> Self::Result {
         count_ratings_combinations(input)
     }
+
+    fn graph(&self, input: &str) -> Option<Graph> {
+        let (instructions, _parts) = parse(input);
+        let mut labels: Vec<&&str> = instructions.keys().collect();
+        labels.sort();
+
+        let mut graph = Graph::new();
+        graph.node("A", "doublecircle", None);
+        graph.node("R", "octagon", None);
+        for label in &labels {
+            let shape = if **label == "in" { "invhouse" } else { "box" };
+            graph.node(label, shape, None);
+        }
+        for label in labels {
+            for op in &instructions[label] {
+                let (condition, retval) = match op {
+                    Op::Lt(attr_index, value, retval) => {
+                        (format!("{}<{value}", ATTRIBUTES[*attr_index]), retval)
+                    }
+                    Op::Gt(attr_index, value, retval) => {
+                        (format!("{}>{value}", ATTRIBUTES[*attr_index]), retval)
+                    }
+                    Op::Return(retval) => ("else".to_string(), retval),
+                };
+                let target = match retval {
+                    RetVal::Accept => "A",
+                    RetVal::Reject => "R",
+                    RetVal::Label(target) => target,
+                };
+                graph.edge(label, target, Some(&condition));
+            }
+        }
+        Some(graph)
+    }
+
+    /// Renames the workflows and swaps the categories around, in the rules
+    /// and the parts alike, then shuffles both.
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        let mut sections = input::sections(input);
+        let mut categories = [0, 1, 2, 3];
+        rng.shuffle(&mut categories);
+        let category = |attr: &str| Some(categories[ATTRIBUTES.iter().position(|&a| a == attr)?]);
+        let mut labels = Labels::new(&["in", "A", "R"]);
+        let mut rename = |label: &str, rng: &mut Rng| {
+            labels.get(label, rng, |_, rng| {
+                let len = rng.range(2..=3) as usize;
+                rng.word(len)
+            })
+        };
+
+        let mut workflows = vec![];
+        for instruction_str in sections.next()?.lines() {
+            let (label, ops_str) = instruction_str.strip_suffix('}')?.split_once('{')?;
+            let mut ops = vec![];
+            for op_str in ops_str.split(',') {
+                let op = match op_str.split_once(':') {
+                    Some((condition, return_value)) => {
+                        let comparison = condition.find(['<', '>'])?;
+                        let (attr, value) = condition.split_at(comparison);
+                        let attr = ATTRIBUTES[category(attr)?];
+                        format!("{attr}{value}:{}", rename(return_value, rng))
+                    }
+                    None => rename(op_str, rng),
+                };
+                ops.push(op);
+            }
+            workflows.push(format!("{}{{{}}}\n", rename(label, rng), ops.join(",")));
+        }
+
+        let mut parts = vec![];
+        for line in sections.next()?.lines() {
+            let mut ratings = [""; 4];
+            for rating in line.strip_prefix('{')?.strip_suffix('}')?.split(',') {
+                let (attr, value) = rating.split_once('=')?;
+                ratings[category(attr)?] = value;
+            }
+            let ratings: Vec<String> = (0..4)
+                .map(|index| format!("{}={}", ATTRIBUTES[index], ratings[index]))
+                .collect();
+            parts.push(format!("{{{}}}\n", ratings.join(",")));
+        }
+
+        rng.shuffle(&mut workflows);
+        rng.shuffle(&mut parts);
+        Some(format!("{}\n{}", workflows.concat(), parts.concat()))
+    }
 }
 
 type Part = Vec<usize>;
@@ -47,18 +133,19 @@ impl<'a> RetVal<'a> {
 
 type Instructions<'a> = HashMap<&'a str, Vec<Op<'a>>>;
 
+const ATTRIBUTES: [&str; 4] = ["x", "m", "a", "s"];
+
 fn attr_index(attr_str: &str) -> usize {
-    match attr_str {
-        "x" => 0,
-        "m" => 1,
-        "a" => 2,
-        "s" => 3,
-        _ => panic!("invalid attribute: {attr_str}"),
-    }
+    ATTRIBUTES
+        .iter()
+        .position(|&attr| attr == attr_str)
+        .unwrap_or_else(|| panic!("invalid attribute: {attr_str}"))
 }
 
-fn parse(input: &str) -> (Instructions, Vec<Part>) {
-    let (instructions_str, parts_str) = input.split_once("\n\n").unwrap();
+fn parse(input: &str) -> (Instructions<'_>, Vec<Part>) {
+    let mut sections = input::sections(input);
+    let instructions_str = sections.next().unwrap();
+    let parts_str = sections.next().unwrap();
 
     let mut instructions = HashMap::new();
     for instruction_str in instructions_str.lines() {
@@ -137,11 +224,8 @@ fn do_instruction(part: &Part, instructions: &Instructions, label: &str) -> bool
     unreachable!("instructions should always return a value");
 }
 
-fn combinations_from_range(rating_ranges: &Vec<(usize, usize)>) -> usize {
-    rating_ranges
-        .iter()
-        .map(|(start, end)| end - start + 1)
-        .product()
+fn combinations_from_range(rating_ranges: &[(usize, usize)]) -> usize {
+    checked::product(rating_ranges.iter().map(|(start, end)| end - start + 1))
 }
 
 fn ratings_combinations(
@@ -235,4 +319,25 @@ hdj{m>838:A,pv}
     fn part_2() {
         assert_eq!(count_ratings_combinations(INPUT_1), 167409079868000);
     }
+
+    #[test]
+    fn graph() {
+        let graph = Day19::new().graph(INPUT_1).unwrap().to_string();
+        assert!(graph.contains(r#""in" [shape=invhouse];"#));
+        assert!(graph.contains(r#""in" -> "px" [label="s<1351"];"#));
+        assert!(graph.contains(r#""in" -> "qqz" [label="else"];"#));
+        assert!(graph.contains(r#""crn" -> "R" [label="else"];"#));
+    }
+
+    #[test]
+    fn messy_input() {
+        for (mess, input) in input::messy_copies(INPUT_1) {
+            assert_eq!(accepted_parts_rating_sum(&input), 19114, "{mess}");
+            assert_eq!(
+                count_ratings_combinations(&input),
+                167409079868000,
+                "{mess}"
+            );
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use crate::anonymize::Rng;
 use crate::Solution;
 
 #[derive(Default)]
@@ -6,15 +7,56 @@ pub struct Day2 {}
 impl Solution for Day2 {
     type Result = usize;
 
-    fn part_1(&self) -> Self::Result {
-        let input = include_str!("data/day2");
+    fn part_1(&self, input: &str) -> Self::Result {
         determine_possible_rounds(input)
     }
 
-    fn part_2(&self) -> Self::Result {
-        let input = include_str!("data/day2");
+    fn part_2(&self, input: &str) -> Self::Result {
         determine_minimum_cubes(input)
     }
+
+    fn explain(&self, part: u8, input: &str) -> Option<String> {
+        let mut lines = vec![];
+        for line in input.lines() {
+            let (game, rest) = line.split_once(": ").unwrap();
+            let (r, g, b) = minimum_cubes(rest);
+            let outcome = if part == 1 {
+                let possible = is_round_possible((r, g, b));
+                if possible { "possible" } else { "impossible" }.to_string()
+            } else {
+                format!("power {}", r * g * b)
+            };
+            lines.push(format!(
+                "{game}: at least {r} red, {g} green, {b} blue ({outcome})"
+            ));
+        }
+        Some(lines.join("\n"))
+    }
+
+    /// Draws new numbers of cubes, up to 20 of each color like the puzzle's
+    /// inputs, and shuffles the colors in each round.
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        let mut anonymized = String::new();
+        for line in input.lines() {
+            let (game, rest) = line.split_once(": ")?;
+            let rounds = rest
+                .split("; ")
+                .map(|round| {
+                    let mut cubes = round
+                        .split(", ")
+                        .map(|cube_str| {
+                            let (_, color) = cube_str.split_once(' ')?;
+                            Some(format!("{} {color}", rng.range(1..=20)))
+                        })
+                        .collect::<Option<Vec<String>>>()?;
+                    rng.shuffle(&mut cubes);
+                    Some(cubes.join(", "))
+                })
+                .collect::<Option<Vec<String>>>()?;
+            anonymized.push_str(&format!("{game}: {}\n", rounds.join("; ")));
+        }
+        Some(anonymized)
+    }
 }
 
 fn determine_possible_rounds(input: &str) -> usize {
@@ -32,10 +74,10 @@ fn determine_possible_rounds(input: &str) -> usize {
     possible_game_ids_sum
 }
 
-type RGB = (usize, usize, usize);
+type Rgb = (usize, usize, usize);
 
-fn cubes_from_round_str(round: &str) -> RGB {
-    let mut cubes: RGB = (0, 0, 0);
+fn cubes_from_round_str(round: &str) -> Rgb {
+    let mut cubes: Rgb = (0, 0, 0);
     for cube_str in round.split(", ") {
         let (count, color) = cube_str.split_once(' ').unwrap();
         let count: usize = count.parse().unwrap();
@@ -49,22 +91,25 @@ fn cubes_from_round_str(round: &str) -> RGB {
     cubes
 }
 
-fn is_round_possible((r, g, b): RGB) -> bool {
+fn is_round_possible((r, g, b): Rgb) -> bool {
     12 >= r && 13 >= g && 14 >= b
 }
 
+fn minimum_cubes(rounds_str: &str) -> Rgb {
+    let mut minimum_set = (0, 0, 0);
+    for cubes in rounds_str.split("; ").map(cubes_from_round_str) {
+        minimum_set.0 = minimum_set.0.max(cubes.0);
+        minimum_set.1 = minimum_set.1.max(cubes.1);
+        minimum_set.2 = minimum_set.2.max(cubes.2);
+    }
+    minimum_set
+}
+
 fn determine_minimum_cubes(input: &str) -> usize {
     let mut power_sum = 0;
     for line in input.lines() {
         let (_, rest) = line.split_once(": ").unwrap();
-        let rounds_str = rest.split("; ");
-        let mut minimum_set = (0, 0, 0);
-        let rounds = rounds_str.map(cubes_from_round_str);
-        for cubes in rounds {
-            minimum_set.0 = minimum_set.0.max(cubes.0);
-            minimum_set.1 = minimum_set.1.max(cubes.1);
-            minimum_set.2 = minimum_set.2.max(cubes.2);
-        }
+        let minimum_set = minimum_cubes(rest);
         power_sum += minimum_set.0 * minimum_set.1 * minimum_set.2
     }
     power_sum
@@ -73,26 +118,29 @@ fn determine_minimum_cubes(input: &str) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input;
 
-    #[test]
-    fn part_1() {
-        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+    const INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
 Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
 
-        assert_eq!(determine_possible_rounds(input), 8);
+    #[test]
+    fn part_1() {
+        assert_eq!(determine_possible_rounds(INPUT), 8);
     }
 
     #[test]
     fn part_2() {
-        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+        assert_eq!(determine_minimum_cubes(INPUT), 2286);
+    }
 
-        assert_eq!(determine_minimum_cubes(input), 2286);
+    #[test]
+    fn messy_input() {
+        for (mess, input) in input::messy_copies(INPUT) {
+            assert_eq!(determine_possible_rounds(&input), 8, "{mess}");
+            assert_eq!(determine_minimum_cubes(&input), 2286, "{mess}");
+        }
     }
 }
@@ -1,26 +1,205 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 
-use crate::Solution;
+use crate::anonymize::{Labels, Rng};
+use crate::dot::Graph;
+use crate::play::Animation;
+use crate::{checked, terminal, trace, Solution};
 
 #[derive(Default)]
 pub struct Day20 {}
 
 impl Solution for Day20 {
-    type Result = usize;
+    type Result = u64;
 
-    fn part_1(&self) -> Self::Result {
-        let input = include_str!("data/day20");
+    fn part_1(&self, input: &str) -> Self::Result {
         simulate_button_presses(input)
     }
 
-    fn part_2(&self) -> Self::Result {
-        let input = include_str!("data/day20");
+    fn part_2(&self, input: &str) -> Self::Result {
         count_presses_for_rx(input)
     }
+
+    fn inspect(&self, input: &str) -> Vec<String> {
+        let modules = parse_modules(input);
+        let mut feeders: Vec<&Module> = modules
+            .values()
+            .filter(|module| module.destinations().contains(&"rx"))
+            .collect();
+        feeders.sort_by_key(|module| module.label());
+
+        let mut checks = vec![];
+        if feeders.is_empty() {
+            checks.push("warning: no module sends to rx".to_string());
+        }
+        for feeder in &feeders {
+            let Module::Conjunction(conjunction) = feeder else {
+                checks.push(format!(
+                    "warning: rx is fed by {}, which isn't a conjunction",
+                    feeder.label()
+                ));
+                continue;
+            };
+            let mut sources = conjunction.sources.clone();
+            sources.sort();
+            checks.push(format!(
+                "rx is fed by conjunction {} with a fan-in of {}: {}",
+                conjunction.label,
+                sources.len(),
+                sources.join(", ")
+            ));
+
+            // Each source is counted as turning on once per cycle of its own,
+            // which holds when it inverts the output of a counter
+            for source in &sources {
+                if !matches!(modules.get(source), Some(Module::Conjunction(_))) {
+                    checks.push(format!(
+                        "warning: part 2 assumes the sources are conjunctions, but {source} isn't"
+                    ));
+                }
+            }
+        }
+        if feeders.len() > 1 {
+            checks.push("warning: part 2 assumes a single module sends to rx".to_string());
+        }
+        checks
+    }
+
+    fn animate(&self, _part: u8, input: &str) -> Animation {
+        let modules = &mut parse_modules(input);
+        let mut animation = Animation::default();
+        for press in 1..=ANIMATED_PRESSES {
+            let mut pulse_number = 0;
+            broadcast(modules, &mut |pulse, queue, modules| {
+                pulse_number += 1;
+                if animation.is_full() {
+                    return;
+                }
+                let caption = format!(
+                    "Press {press}, pulse {pulse_number}: {}",
+                    format_pulse(pulse)
+                );
+                animation.push(caption, format_state(queue, modules));
+            });
+        }
+        animation
+    }
+
+    fn graph(&self, input: &str) -> Option<Graph> {
+        let modules = parse_modules(input);
+        let mut labels: Vec<&&str> = modules.keys().collect();
+        labels.sort();
+
+        let mut graph = Graph::new();
+        graph.node("button", "plaintext", None);
+        graph.edge("button", "broadcaster", None);
+        for label in &labels {
+            let (shape, prefix) = match modules[**label] {
+                Module::Broadcaster(_) => ("invtriangle", ""),
+                Module::FlipFlop(_) => ("box", "%"),
+                Module::Conjunction(_) => ("diamond", "&"),
+            };
+            graph.node(label, shape, Some(&format!("{prefix}{label}")));
+        }
+        // Modules that only receive pulses, like rx
+        let mut sinks: Vec<&str> = modules
+            .values()
+            .flat_map(|module| module.destinations().iter().copied())
+            .filter(|destination| !modules.contains_key(destination))
+            .collect();
+        sinks.sort();
+        sinks.dedup();
+        for sink in sinks {
+            graph.node(sink, "doublecircle", None);
+        }
+
+        for label in labels {
+            for destination in modules[*label].destinations() {
+                graph.edge(label, destination, None);
+            }
+        }
+        Some(graph)
+    }
+
+    /// Renames every module but the broadcaster and rx, and shuffles the
+    /// order they're listed in. Which modules send to which, and in what
+    /// order, stays the same.
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        let mut labels = Labels::new(&["broadcaster", "rx"]);
+        let mut rename = |label: &str, rng: &mut Rng| labels.get(label, rng, |_, rng| rng.word(2));
+
+        let mut lines = vec![];
+        for module_str in input.lines() {
+            let (label, destinations_str) = module_str.split_once(" -> ")?;
+            let (prefix, label) = match label.strip_prefix(['%', '&']) {
+                Some(rest) => (&label[..1], rest),
+                None => ("", label),
+            };
+            let destinations: Vec<String> = destinations_str
+                .split(", ")
+                .map(|destination| rename(destination, rng))
+                .collect();
+            lines.push(format!(
+                "{prefix}{} -> {}\n",
+                rename(label, rng),
+                destinations.join(", ")
+            ));
+        }
+        rng.shuffle(&mut lines);
+        Some(lines.concat())
+    }
+}
+
+type Pulse<'a> = (bool, &'a str, &'a str);
+
+fn format_pulse((signal, source, target): Pulse) -> String {
+    let (color, level) = if signal {
+        (terminal::RED, "high")
+    } else {
+        (terminal::BLUE, "low")
+    };
+    format!("{source} {color}-{level}->{} {target}", terminal::RESET)
+}
+
+fn format_state(queue: &VecDeque<Pulse>, modules: &HashMap<&str, Module>) -> String {
+    let mut lines = vec!["Queued pulses:".to_string()];
+    lines.extend(
+        queue
+            .iter()
+            .map(|&pulse| format!("  {}", format_pulse(pulse))),
+    );
+
+    let mut labels: Vec<&&str> = modules.keys().collect();
+    labels.sort();
+    let mut flip_flops_on = vec![];
+    let mut conjunctions = vec![];
+    for label in labels {
+        match &modules[label] {
+            Module::FlipFlop(module) if module.state => flip_flops_on.push(module.label),
+            Module::Conjunction(module) => {
+                let mut high_sources: Vec<&str> = module.state.iter().copied().collect();
+                high_sources.sort();
+                conjunctions.push(format!(
+                    "  {} remembers high from [{}]",
+                    module.label,
+                    high_sources.join(", ")
+                ));
+            }
+            _ => (),
+        }
+    }
+
+    lines.push(String::new());
+    lines.push(format!("Flip-flops on: {}", flip_flops_on.join(", ")));
+    lines.push("Conjunctions:".to_string());
+    lines.extend(conjunctions);
+    lines.join("\n")
 }
 
+// Presses are animated pulse by pulse, so only the first few are shown
+const ANIMATED_PRESSES: usize = 4;
+
 trait Signalable<'a> {
-    fn label(&self) -> &str;
+    fn label(&self) -> &'a str;
     fn destinations(&self) -> &Vec<&'a str>;
     fn send(&mut self, signal: bool, source: &'a str) -> Option<bool>;
 }
@@ -32,7 +211,7 @@ struct Broadcaster<'a> {
 }
 
 impl<'a> Signalable<'a> for Broadcaster<'a> {
-    fn label(&self) -> &str {
+    fn label(&self) -> &'a str {
         self.label
     }
     fn destinations(&self) -> &Vec<&'a str> {
@@ -51,7 +230,7 @@ struct FlipFlop<'a> {
 }
 
 impl<'a> Signalable<'a> for FlipFlop<'a> {
-    fn label(&self) -> &str {
+    fn label(&self) -> &'a str {
         self.label
     }
     fn destinations(&self) -> &Vec<&'a str> {
@@ -102,7 +281,7 @@ enum Module<'a> {
 // So much boilerplate...
 
 impl<'a> Signalable<'a> for Module<'a> {
-    fn label(&self) -> &str {
+    fn label(&self) -> &'a str {
         match self {
             Self::Broadcaster(m) => m.label(),
             Self::FlipFlop(m) => m.label(),
@@ -125,7 +304,7 @@ impl<'a> Signalable<'a> for Module<'a> {
     }
 }
 
-fn parse_modules(input: &str) -> HashMap<&str, Module> {
+fn parse_modules(input: &str) -> HashMap<&str, Module<'_>> {
     let mut modules = HashMap::new();
 
     // Keep track of these so we can assign sources to them later
@@ -159,13 +338,13 @@ fn parse_modules(input: &str) -> HashMap<&str, Module> {
             }
             _ => panic!("invalid module: {label}"),
         };
-        modules.insert(label, module);
+        modules.insert(module.label(), module);
     }
 
     for (source_label, module) in modules.iter() {
         for destination_label in module.destinations() {
             conjunction_modules_sources
-                .entry(&destination_label)
+                .entry(destination_label)
                 .and_modify(|sources| sources.push(source_label));
         }
     }
@@ -181,26 +360,27 @@ fn parse_modules(input: &str) -> HashMap<&str, Module> {
     modules
 }
 
-fn broadcast<F>(modules: &mut HashMap<&str, Module>, check_output: &mut F) -> (usize, usize)
+/// Presses the button and sends pulses until they have all been handled,
+/// calling `on_pulse` with each pulse as it's handled, the pulses queued
+/// behind it and the modules' state.
+fn broadcast<'a, F>(modules: &mut HashMap<&'a str, Module<'a>>, on_pulse: &mut F) -> (u64, u64)
 where
-    F: FnMut((bool, &str)),
+    F: FnMut(Pulse<'a>, &VecDeque<Pulse<'a>>, &HashMap<&'a str, Module<'a>>),
 {
     let mut signals_sent = (0, 0);
 
-    let mut signals_queue: VecDeque<(bool, &str, &str)> = VecDeque::new();
+    let mut signals_queue: VecDeque<Pulse> = VecDeque::new();
     signals_queue.push_back((false, "button", "broadcaster"));
 
-    while signals_queue.len() > 0 {
-        let (signal, source, target) = signals_queue.pop_front().unwrap();
+    while let Some((signal, source, target)) = signals_queue.pop_front() {
+        on_pulse((signal, source, target), &signals_queue, modules);
 
-        check_output((signal, source));
-
-        // println!(
-        //     "{} {} {}",
-        //     source,
-        //     if signal { "high" } else { "low" },
-        //     target
-        // );
+        trace::trace!(
+            "{} -{}-> {}",
+            source,
+            if signal { "high" } else { "low" },
+            target
+        );
 
         if signal {
             signals_sent.1 += 1;
@@ -222,52 +402,62 @@ where
     signals_sent
 }
 
-fn simulate_button_presses(input: &str) -> usize {
+fn simulate_button_presses(input: &str) -> u64 {
     let modules = &mut parse_modules(input);
     let mut total_low_signals_sent = 0;
     let mut total_high_signals_sent = 0;
     for _ in 0..1000 {
-        let (low_signals_sent, high_signals_sent) = broadcast(modules, &mut |_| {});
+        let (low_signals_sent, high_signals_sent) = broadcast(modules, &mut |_, _, _| {});
         total_low_signals_sent += low_signals_sent;
         total_high_signals_sent += high_signals_sent;
     }
-    total_low_signals_sent * total_high_signals_sent
+    checked::mul(total_low_signals_sent, total_high_signals_sent)
 }
 
-fn count_presses_for_rx(input: &str) -> usize {
+fn count_presses_for_rx(input: &str) -> u64 {
     let modules = &mut parse_modules(input);
 
-    // "rx" depends on a conjunction module "lg" that is sourced by
-    // "vg", "nb", "vc", and "ls". So we need to keep track of how
-    // many presses it takes to turn on each of these, then take the
+    // "rx" depends on a single conjunction module, which only sends it a
+    // low pulse once all of its sources have sent it a high one. Each
+    // source does so on a cycle of its own, so we need to keep track of
+    // how many presses it takes to turn on each of these, then take the
     // product of those presses.
+    let sources = rx_feeder_sources(modules);
 
-    let mut presses = [0, 0, 0, 0];
+    let mut presses = vec![0; sources.len()];
     let mut current_presses = 0;
-    while presses.iter().any(|&n| n == 0) {
+    while presses.contains(&0) {
         current_presses += 1;
 
-        broadcast(modules, &mut |(signal, source)| {
-            if signal && presses[0] == 0 && source == "vg" {
-                presses[0] = current_presses;
-            }
-            if signal && presses[1] == 0 && source == "nb" {
-                presses[1] = current_presses;
-            }
-            if signal && presses[2] == 0 && source == "vc" {
-                presses[2] = current_presses;
+        broadcast(modules, &mut |(signal, source, _), _, _| {
+            if !signal {
+                return;
             }
-            if signal && presses[3] == 0 && source == "ls" {
-                presses[3] = current_presses;
+            if let Some(index) = sources.iter().position(|&s| s == source) {
+                if presses[index] == 0 {
+                    presses[index] = current_presses;
+                }
             }
         });
     }
-    presses.iter().product()
+    checked::product(presses)
+}
+
+/// Returns the sources of the conjunction that sends to "rx".
+fn rx_feeder_sources<'a>(modules: &HashMap<&'a str, Module<'a>>) -> Vec<&'a str> {
+    let feeder = modules
+        .values()
+        .find(|module| module.destinations().contains(&"rx"));
+    match feeder {
+        Some(Module::Conjunction(conjunction)) => conjunction.sources.clone(),
+        _ => panic!("part 2 needs a conjunction module that sends to rx"),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input;
 
     const INPUT_1: &str = "broadcaster -> a, b, c
 %a -> b
@@ -286,4 +476,22 @@ mod tests {
         assert_eq!(simulate_button_presses(INPUT_1), 32000000);
         assert_eq!(simulate_button_presses(INPUT_2), 11687500);
     }
+
+    #[test]
+    fn animate() {
+        let animation = Day20::new().animate(1, INPUT_1);
+        // Each press of the first example sends 12 pulses
+        assert_eq!(animation.frames.len(), 12 * ANIMATED_PRESSES);
+        assert!(animation.frames[1].body.contains("broadcaster"));
+    }
+
+    #[test]
+    fn messy_input() {
+        for (mess, input) in input::messy_copies(INPUT_1) {
+            assert_eq!(simulate_button_presses(&input), 32000000, "{mess}");
+        }
+        for (mess, input) in input::messy_copies(INPUT_2) {
+            assert_eq!(simulate_button_presses(&input), 11687500, "{mess}");
+        }
+    }
 }
@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use crate::anonymize::{Rng, Symmetry};
+use crate::grid::{Grid, Position};
 use crate::Solution;
 
 #[derive(Default)]
@@ -8,15 +10,88 @@ pub struct Day3 {}
 impl Solution for Day3 {
     type Result = usize;
 
-    fn part_1(&self) -> Self::Result {
-        let input = include_str!("data/day3");
+    fn part_1(&self, input: &str) -> Self::Result {
         part_numbers_sum(input)
     }
 
-    fn part_2(&self) -> Self::Result {
-        let input = include_str!("data/day3");
+    fn part_2(&self, input: &str) -> Self::Result {
         gear_ratios_sum(input)
     }
+
+    /// Mirrors the schematic and redraws its numbers and symbols. Gears stay
+    /// gears, and numbers stay in rows so they can still be read. Numbers
+    /// next to the same `*` are drawn to be different, since a gear's numbers
+    /// are told apart by their values.
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        let symmetry = Symmetry {
+            transpose: false,
+            ..Symmetry::random(rng)
+        };
+        let mut rows: Vec<Vec<char>> = symmetry
+            .apply(input, |ch| ch)
+            .lines()
+            .map(|line| line.chars().collect())
+            .collect();
+
+        let stars: Vec<Position> = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|&(_, &ch)| ch == '*')
+                    .map(move |(j, _)| (i, j))
+            })
+            .collect();
+
+        // Each number drawn so far, with the stars next to it
+        let mut drawn: Vec<(Vec<Position>, String)> = vec![];
+        for (i, row) in rows.iter_mut().enumerate() {
+            let mut j = 0;
+            while j < row.len() {
+                let len = row[j..].iter().take_while(|ch| ch.is_ascii_digit()).count();
+                if len == 0 {
+                    j += 1;
+                    continue;
+                }
+
+                let next_to: Vec<Position> = stars
+                    .iter()
+                    .copied()
+                    .filter(|&(y, x)| y.abs_diff(i) <= 1 && x + 1 >= j && x <= j + len)
+                    .collect();
+                let number = (0..100)
+                    .map(|_| {
+                        (0..len)
+                            .map(|k| {
+                                let first = if k == 0 { 1 } else { 0 };
+                                char::from(b'0' + rng.range(first..=9) as u8)
+                            })
+                            .collect::<String>()
+                    })
+                    .find(|number| {
+                        !drawn.iter().any(|(stars, other)| {
+                            other == number && stars.iter().any(|star| next_to.contains(star))
+                        })
+                    })?;
+                row.splice(j..j + len, number.chars());
+                drawn.push((next_to, number));
+                j += len;
+            }
+        }
+
+        let mut anonymized = String::new();
+        for row in rows {
+            for ch in row {
+                anonymized.push(match ch {
+                    '0'..='9' | '.' | '*' => ch,
+                    _ => *rng.pick(&['#', '$', '%', '&', '+', '-', '/', '=', '@']),
+                });
+            }
+            anonymized.push('\n');
+        }
+        Some(anonymized)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -26,103 +101,71 @@ enum Item {
     Empty,
 }
 
-struct Grid {
-    inner: Vec<Vec<Item>>,
-    width: usize,
-    height: usize,
+fn parse(input: &str) -> Grid<Item> {
+    Grid::from_rows(input.lines().map(items_from_line).collect())
 }
 
-impl Grid {
-    fn from_input(input: &str) -> Self {
-        let inner: Vec<Vec<Item>> = input.lines().map(Self::items_from_line).collect();
-
-        Self {
-            width: inner[0].len(),
-            height: inner.len(),
-            inner,
-        }
-    }
-
-    fn items_from_line(line: &str) -> Vec<Item> {
-        let mut items = vec![Item::Empty; line.len()];
+fn items_from_line(line: &str) -> Vec<Item> {
+    let mut items = vec![Item::Empty; line.len()];
 
-        let mut part_number_start_position = None;
-        let mut part_number = 0;
-        for (index, ch) in line.chars().enumerate() {
-            if let Some(digit) = ch.to_digit(10) {
-                if part_number_start_position.is_none() {
-                    part_number_start_position = Some(index);
-                }
-                part_number = part_number * 10 + digit as usize;
-
-                continue;
-            }
-
-            if let Some(start_position) = part_number_start_position {
-                for i in start_position..index {
-                    items[i] = Item::PartNumber(part_number);
-                }
-                part_number_start_position = None;
-                part_number = 0;
+    let mut part_number_start_position = None;
+    let mut part_number = 0;
+    for (index, ch) in line.chars().enumerate() {
+        if let Some(digit) = ch.to_digit(10) {
+            if part_number_start_position.is_none() {
+                part_number_start_position = Some(index);
             }
+            part_number = part_number * 10 + digit as usize;
 
-            if ch == '.' {
-                items[index] = Item::Empty
-            } else {
-                items[index] = Item::Symbol(ch);
-            }
+            continue;
         }
 
         if let Some(start_position) = part_number_start_position {
-            for i in start_position..line.len() {
-                items[i] = Item::PartNumber(part_number);
-            }
+            items[start_position..index].fill(Item::PartNumber(part_number));
+            part_number_start_position = None;
+            part_number = 0;
         }
 
-        items
+        if ch == '.' {
+            items[index] = Item::Empty
+        } else {
+            items[index] = Item::Symbol(ch);
+        }
     }
 
-    fn get(&self, x: usize, y: usize) -> Option<&Item> {
-        self.inner.get(y).and_then(|row| row.get(x))
+    if let Some(start_position) = part_number_start_position {
+        items[start_position..].fill(Item::PartNumber(part_number));
     }
 
-    fn filter_adjacent_items<F>(&self, x: usize, y: usize, filter: F) -> impl Iterator<Item = &Item>
-    where
-        F: Fn(&&Item) -> bool,
-    {
-        let w = self.width - 1;
-        let h = self.height - 1;
-
-        #[rustfmt::skip]
-        let surrounding_items = [
-            if x > 0 && y > 0 { self.get(x - 1, y - 1) } else { None },
-            if x > 0          { self.get(x - 1, y) } else { None },
-            if x > 0 && y < h { self.get(x - 1, y + 1) } else { None },
-            if          y > 0 { self.get(x, y - 1) } else { None },
-            if          y < h { self.get(x, y + 1) } else { None },
-            if x < w && y > 0 { self.get(x + 1, y - 1) } else { None },
-            if x < w          { self.get(x + 1, y) } else { None },
-            if x < w && y < h { self.get(x + 1, y + 1) } else { None },
-        ];
-
-        surrounding_items.into_iter().flatten().filter(filter)
-    }
+    items
+}
+
+fn adjacent_items<F>(
+    grid: &Grid<Item>,
+    position: Position,
+    filter: F,
+) -> impl Iterator<Item = &Item>
+where
+    F: Fn(&&Item) -> bool,
+{
+    grid.neighbours8(position)
+        .map(|position| &grid[position])
+        .filter(filter)
 }
 
 fn part_numbers_sum(input: &str) -> usize {
     let mut sum = 0;
 
-    let grid = Grid::from_input(input);
+    let grid = parse(input);
 
     let mut part_number = 0;
     let mut has_symbol = false;
-    for y in 0..grid.height {
-        for x in 0..grid.width {
-            if let Item::PartNumber(number) = grid.get(x, y).unwrap() {
-                part_number = *number;
+    for i in 0..grid.height() {
+        for j in 0..grid.width() {
+            if let Item::PartNumber(number) = grid[(i, j)] {
+                part_number = number;
                 has_symbol = has_symbol
-                    || grid
-                        .filter_adjacent_items(x, y, |item| matches!(item, Item::Symbol(_)))
+                    || adjacent_items(&grid, (i, j), |item| matches!(item, Item::Symbol(_)))
                         .next()
                         .is_some();
             } else {
@@ -146,29 +189,26 @@ fn part_numbers_sum(input: &str) -> usize {
 fn gear_ratios_sum(input: &str) -> usize {
     let mut sum = 0;
 
-    let grid = Grid::from_input(input);
-
-    for y in 0..grid.height {
-        for x in 0..grid.width {
-            if grid.get(x, y).unwrap() != &Item::Symbol('*') {
-                continue;
-            };
+    let grid = parse(input);
 
-            let part_numbers: HashSet<&Item> = grid
-                .filter_adjacent_items(x, y, |item| matches!(item, Item::PartNumber(_)))
-                .collect();
+    for (position, item) in grid.iter() {
+        if item != &Item::Symbol('*') {
+            continue;
+        };
 
-            if part_numbers.len() != 2 {
-                continue;
-            }
+        let part_numbers: HashSet<&Item> =
+            adjacent_items(&grid, position, |item| matches!(item, Item::PartNumber(_))).collect();
 
-            sum += part_numbers.iter().fold(1, |acc, item| {
-                let Item::PartNumber(part_number) = item else {
-                    panic!("expected part number");
-                };
-                acc * part_number
-            });
+        if part_numbers.len() != 2 {
+            continue;
         }
+
+        sum += part_numbers.iter().fold(1, |acc, item| {
+            let Item::PartNumber(part_number) = item else {
+                panic!("expected part number");
+            };
+            acc * part_number
+        });
     }
 
     sum
@@ -177,6 +217,7 @@ fn gear_ratios_sum(input: &str) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input;
 
     const INPUT: &str = "467..114..
 ...*......
@@ -198,4 +239,97 @@ mod tests {
     fn part_2() {
         assert_eq!(gear_ratios_sum(INPUT), 467835);
     }
+
+    #[test]
+    fn messy_input() {
+        for (mess, input) in input::messy_copies(INPUT) {
+            assert_eq!(part_numbers_sum(&input), 4361, "{mess}");
+            assert_eq!(gear_ratios_sum(&input), 467835, "{mess}");
+        }
+    }
+
+    /// Counts the part numbers and gears in a schematic, telling numbers apart
+    /// by where they are rather than by their values, and sums the gear
+    /// ratios the same way.
+    fn count(input: &str) -> (usize, usize, usize) {
+        let rows: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+        let mut numbers = vec![];
+        for (i, row) in rows.iter().enumerate() {
+            let mut j = 0;
+            while j < row.len() {
+                let len = row[j..].iter().take_while(|b| b.is_ascii_digit()).count();
+                if len > 0 {
+                    let value: usize = input.lines().nth(i).unwrap()[j..j + len].parse().unwrap();
+                    numbers.push((i, j, len, value));
+                }
+                j += len.max(1);
+            }
+        }
+
+        let next_to = |&(i, j, len, _): &(usize, usize, usize, usize), (y, x): Position| {
+            y.abs_diff(i) <= 1 && x + 1 >= j && x <= j + len
+        };
+        let symbols: Vec<(Position, u8)> = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, &b)| ((y, x), b)))
+            .filter(|&(_, b)| b != b'.' && !b.is_ascii_digit())
+            .collect();
+
+        let part_numbers = numbers
+            .iter()
+            .filter(|number| symbols.iter().any(|&(symbol, _)| next_to(number, symbol)))
+            .count();
+        let gears: Vec<Vec<usize>> = symbols
+            .iter()
+            .filter(|&&(_, b)| b == b'*')
+            .map(|&(star, _)| {
+                numbers
+                    .iter()
+                    .filter(|number| next_to(number, star))
+                    .map(|number| number.3)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|values| values.len() == 2)
+            .collect();
+        let ratios = gears.iter().map(|values| values[0] * values[1]).sum();
+        (part_numbers, gears.len(), ratios)
+    }
+
+    #[test]
+    fn anonymize_keeps_gears() {
+        // Numbers of one to three digits scattered between symbols, like a
+        // real schematic, with plenty of gears between small numbers
+        let mut rng = Rng::new(3);
+        let mut input = String::new();
+        for _ in 0..60 {
+            let mut row = String::new();
+            while row.len() < 60 {
+                match rng.range(0..=9) {
+                    0..=2 => {
+                        let len = rng.range(1..=3) as u32;
+                        row += &rng
+                            .range(10_u64.pow(len - 1)..=10_u64.pow(len) - 1)
+                            .to_string();
+                        row.push('.');
+                    }
+                    3 => row.push(*rng.pick(&['*', '*', '#', '$', '+'])),
+                    _ => row.push('.'),
+                }
+            }
+            row.truncate(60);
+            input += &row;
+            input.push('\n');
+        }
+        let (part_numbers, gears, _) = count(&input);
+        assert!(gears > 20);
+
+        for seed in 0..8 {
+            let anonymized = Day3::new().anonymize(&input, &mut Rng::new(seed)).unwrap();
+            assert_ne!(anonymized, input);
+            let (new_part_numbers, new_gears, ratios) = count(&anonymized);
+            assert_eq!((new_part_numbers, new_gears), (part_numbers, gears));
+            assert_eq!(gear_ratios_sum(&anonymized), ratios);
+        }
+    }
 }
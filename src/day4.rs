@@ -1,4 +1,5 @@
-use crate::Solution;
+use crate::anonymize::Rng;
+use crate::{checked, Solution};
 
 #[derive(Default)]
 pub struct Day4 {}
@@ -6,15 +7,51 @@ pub struct Day4 {}
 impl Solution for Day4 {
     type Result = usize;
 
-    fn part_1(&self) -> Self::Result {
-        let input = include_str!("data/day4");
+    fn part_1(&self, input: &str) -> Self::Result {
         count_points(input)
     }
 
-    fn part_2(&self) -> Self::Result {
-        let input = include_str!("data/day4");
+    fn part_2(&self, input: &str) -> Self::Result {
         count_cards(input)
     }
+
+    /// Swaps numbers for others throughout, which keeps how many numbers
+    /// each card matches, and shuffles each card's numbers. Cards keep their
+    /// order, since a card never wins copies of cards past the last one.
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        let mut numbers: Vec<u32> = (1..=99).collect();
+        rng.shuffle(&mut numbers);
+
+        let mut anonymized = String::new();
+        for line in input.lines() {
+            let (card_label, rest) = line.split_once(':')?;
+            let (winning_numbers, drawn_numbers) = rest.split_once('|')?;
+            let mut swap = |numbers_str: &str| -> Option<String> {
+                let mut swapped = numbers_str
+                    .split_ascii_whitespace()
+                    .map(|n| {
+                        numbers
+                            .get(n.parse::<usize>().ok()?.checked_sub(1)?)
+                            .copied()
+                    })
+                    .collect::<Option<Vec<u32>>>()?;
+                rng.shuffle(&mut swapped);
+                Some(
+                    swapped
+                        .iter()
+                        .map(|n| format!("{n:>2}"))
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                )
+            };
+            let winning_numbers = swap(winning_numbers)?;
+            let drawn_numbers = swap(drawn_numbers)?;
+            anonymized.push_str(&format!(
+                "{card_label}: {winning_numbers} | {drawn_numbers}\n"
+            ));
+        }
+        Some(anonymized)
+    }
 }
 
 struct Numbers(u128);
@@ -24,7 +61,7 @@ impl Numbers {
         let numbers = numbers_str
             .split_ascii_whitespace()
             .map(|n| n.parse().unwrap());
-        let bits: u128 = numbers.fold(0, |bits, number: u8| bits | (1 << number));
+        let bits: u128 = numbers.fold(0, |bits, number: u32| bits | checked::shl(1, number));
         Self(bits)
     }
 
@@ -79,8 +116,8 @@ fn count_cards(input: &str) -> usize {
     for (index, card) in cards.iter().enumerate() {
         let matches = card.matching_numbers() as usize;
         let current_card_quantity = card_quantities[index];
-        for new_card_index in (index + 1)..=(index + matches) {
-            card_quantities[new_card_index] += current_card_quantity;
+        for quantity in &mut card_quantities[(index + 1)..=(index + matches)] {
+            *quantity += current_card_quantity;
         }
     }
 
@@ -90,6 +127,7 @@ fn count_cards(input: &str) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input;
 
     const INPUT: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
 Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
@@ -107,4 +145,12 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
     fn part_2() {
         assert_eq!(count_cards(INPUT), 30);
     }
+
+    #[test]
+    fn messy_input() {
+        for (mess, input) in input::messy_copies(INPUT) {
+            assert_eq!(count_points(&input), 13, "{mess}");
+            assert_eq!(count_cards(&input), 30, "{mess}");
+        }
+    }
 }
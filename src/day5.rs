@@ -1,6 +1,7 @@
 use std::ops::Range;
 
-use crate::Solution;
+use crate::anonymize::Rng;
+use crate::{input, parallel, Solution};
 
 #[derive(Default)]
 pub struct Day5 {}
@@ -8,15 +9,49 @@ pub struct Day5 {}
 impl Solution for Day5 {
     type Result = usize;
 
-    fn part_1(&self) -> Self::Result {
-        let input = include_str!("data/day5");
+    fn part_1(&self, input: &str) -> Self::Result {
         find_min_location_v1(input)
     }
 
-    fn part_2(&self) -> Self::Result {
-        let input = include_str!("data/day5");
+    fn part_2(&self, input: &str) -> Self::Result {
         find_min_location_v2(input)
     }
+
+    /// Moves every seed and range by the same amount, which moves the
+    /// locations by that amount too, and shuffles the ranges in each map.
+    /// Seed range lengths are left alone so part 2 takes as long as before.
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        let offset = rng.range(0..=1_000_000_000) as usize;
+        let (seeds, maps) = parse_input(input);
+
+        let seeds: Vec<String> = seeds
+            .iter()
+            .enumerate()
+            .map(|(index, seed)| if index % 2 == 0 { seed + offset } else { *seed }.to_string())
+            .collect();
+        let mut anonymized = format!("seeds: {}\n", seeds.join(" "));
+
+        let headers = input::sections(input)
+            .skip(1)
+            .map(|section| section.lines().next());
+        for (header, map) in headers.zip(maps) {
+            let mut ranges: Vec<String> = map
+                .0
+                .iter()
+                .map(|range| {
+                    format!(
+                        "{} {} {}",
+                        range.destinations.start + offset,
+                        range.sources.start + offset,
+                        range.sources.len()
+                    )
+                })
+                .collect();
+            rng.shuffle(&mut ranges);
+            anonymized.push_str(&format!("\n{}\n{}\n", header?, ranges.join("\n")));
+        }
+        Some(anonymized)
+    }
 }
 
 #[derive(Debug)]
@@ -71,16 +106,15 @@ impl Map {
 }
 
 fn parse_input(input: &str) -> (Vec<usize>, Vec<Map>) {
-    let (seeds_section, rest) = input.split_once("\n\n").unwrap();
-    let (_label, seeds_str) = seeds_section.split_once(':').unwrap();
+    let mut sections = input::sections(input);
+    let (_label, seeds_str) = sections.next().unwrap().split_once(':').unwrap();
     let seeds = seeds_str
         .trim()
         .split_ascii_whitespace()
         .map(|seed_str| seed_str.parse().unwrap())
         .collect();
 
-    let maps = rest
-        .split("\n\n")
+    let maps = sections
         .map(|section_with_header| {
             let (_header, section) = section_with_header.split_once('\n').unwrap();
             Map::from_str(section)
@@ -100,26 +134,34 @@ fn find_min_location_v1(input: &str) -> usize {
     locations.min().unwrap()
 }
 
+// Seed ranges are split into pieces of at most this many seeds, so that the
+// work is shared out evenly between threads
+const SEEDS_PER_PIECE: usize = 1 << 20;
+
 fn find_min_location_v2(input: &str) -> usize {
     let (seeds, maps) = parse_input(input);
 
-    let mut min_location = usize::MAX;
-
+    let mut pieces = vec![];
     for chunk in seeds.chunks(2) {
         let seed_start = chunk[0];
-        let length = chunk[1];
-        let seeds = seed_start..(seed_start + length);
+        let seed_end = seed_start + chunk[1];
+        pieces.extend(
+            (seed_start..seed_end)
+                .step_by(SEEDS_PER_PIECE)
+                .map(|start| start..seed_end.min(start + SEEDS_PER_PIECE)),
+        );
+    }
 
-        let min_chunk_location = seeds
-            .into_iter()
+    parallel::map(&pieces, |seeds| {
+        seeds
+            .clone()
             .map(|seed| maps.iter().fold(seed, |source, map| map.lookup(source)))
             .min()
-            .unwrap();
-
-        min_location = min_location.min(min_chunk_location);
-    }
-
-    min_location
+            .unwrap_or(usize::MAX)
+    })
+    .into_iter()
+    .min()
+    .unwrap()
 }
 
 #[cfg(test)]
@@ -169,4 +211,12 @@ humidity-to-location map:
     fn part_2() {
         assert_eq!(find_min_location_v2(INPUT), 46);
     }
+
+    #[test]
+    fn messy_input() {
+        for (mess, input) in input::messy_copies(INPUT) {
+            assert_eq!(find_min_location_v1(&input), 35, "{mess}");
+            assert_eq!(find_min_location_v2(&input), 46, "{mess}");
+        }
+    }
 }
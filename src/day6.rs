@@ -1,26 +1,79 @@
-use crate::Solution;
+use crate::anonymize::Rng;
+use crate::{checked, Solution};
 
 #[derive(Default)]
 pub struct Day6 {}
 
 impl Solution for Day6 {
-    type Result = usize;
+    type Result = u64;
 
-    fn part_1(&self) -> Self::Result {
-        let input = include_str!("data/day6");
+    fn part_1(&self, input: &str) -> Self::Result {
         count_options_v1(input)
     }
 
-    fn part_2(&self) -> Self::Result {
-        let input = include_str!("data/day6");
+    fn part_2(&self, input: &str) -> Self::Result {
         count_options_v2(input)
     }
+
+    /// Draws new times and record distances with as many digits as the
+    /// originals, keeping every race winnable, including the one long race
+    /// of part 2.
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        let (times_line, distances_line) = parse_lines(input);
+        let (_, times_str) = times_line.split_once(':')?;
+        let (_, distances_str) = distances_line.split_once(':')?;
+        let races: Vec<(&str, &str)> = times_str
+            .split_ascii_whitespace()
+            .zip(distances_str.split_ascii_whitespace())
+            .collect();
+
+        let mut draw = |digits: usize, max: u64| {
+            let min = 10_u64.pow(digits as u32 - 1);
+            let max = max.min(10_u64.pow(digits as u32) - 1);
+            if min <= max {
+                rng.range(min..=max)
+            } else {
+                rng.range(1..=max.max(1))
+            }
+        };
+        for _ in 0..100 {
+            let mut times = vec![];
+            let mut distances = vec![];
+            for (time, distance) in &races {
+                // Shorter races can't beat even the shortest record
+                let new_time = draw(time.len(), u64::MAX).max(3);
+                let new_distance = draw(distance.len(), new_time * new_time / 4 - 1);
+                times.push(new_time.to_string());
+                distances.push(new_distance.to_string());
+            }
+
+            let time: u64 = times.concat().parse().ok()?;
+            let distance: u64 = distances.concat().parse().ok()?;
+            if time
+                .checked_mul(time)
+                .is_some_and(|square| distance < square / 4)
+            {
+                let width = times.iter().chain(&distances).map(String::len).max()?;
+                let row = |numbers: &[String]| {
+                    let numbers: Vec<String> =
+                        numbers.iter().map(|n| format!("{n:>width$}")).collect();
+                    numbers.join("  ")
+                };
+                return Some(format!(
+                    "Time:     {}\nDistance: {}\n",
+                    row(&times),
+                    row(&distances)
+                ));
+            }
+        }
+        None
+    }
 }
 
-fn count_race_options(time: usize, distance: usize) -> usize {
+fn count_race_options(time: u64, distance: u64) -> u64 {
     let mut options = 0;
     for t in 1..time {
-        let d = t * (time - t);
+        let d = checked::mul(t, time - t);
         if d > distance {
             options += 1;
         }
@@ -28,8 +81,13 @@ fn count_race_options(time: usize, distance: usize) -> usize {
     options
 }
 
-fn count_options_v1(input: &str) -> usize {
-    let (times_line, distances_line) = input.trim().split_once("\n").unwrap();
+fn parse_lines(input: &str) -> (&str, &str) {
+    let mut lines = input.lines();
+    (lines.next().unwrap(), lines.next().unwrap())
+}
+
+fn count_options_v1(input: &str) -> u64 {
+    let (times_line, distances_line) = parse_lines(input);
     let (_, times_str) = times_line.split_once(':').unwrap();
     let (_, distances_str) = distances_line.split_once(':').unwrap();
     let times = times_str
@@ -44,14 +102,14 @@ fn count_options_v1(input: &str) -> usize {
 
     for (time, distance) in races {
         let options = count_race_options(time, distance);
-        product *= options;
+        product = checked::mul(product, options);
     }
 
     product
 }
 
-fn count_options_v2(input: &str) -> usize {
-    let (times_line, distances_line) = input.trim().split_once("\n").unwrap();
+fn count_options_v2(input: &str) -> u64 {
+    let (times_line, distances_line) = parse_lines(input);
     let (_, times_str) = times_line.split_once(':').unwrap();
     let (_, distances_str) = distances_line.split_once(':').unwrap();
     let time = times_str.replace(" ", "").parse().unwrap();
@@ -63,6 +121,7 @@ fn count_options_v2(input: &str) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input;
 
     const INPUT: &str = "Time:      7  15   30
 Distance:  9  40  200";
@@ -76,4 +135,12 @@ Distance:  9  40  200";
     fn part_2() {
         assert_eq!(count_options_v2(INPUT), 71503);
     }
+
+    #[test]
+    fn messy_input() {
+        for (mess, input) in input::messy_copies(INPUT) {
+            assert_eq!(count_options_v1(&input), 288, "{mess}");
+            assert_eq!(count_options_v2(&input), 71503, "{mess}");
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use crate::anonymize::Rng;
 use crate::Solution;
 
 #[derive(Default)]
@@ -6,17 +7,67 @@ pub struct Day7 {}
 impl Solution for Day7 {
     type Result = usize;
 
-    fn part_1(&self) -> Self::Result {
-        let input = include_str!("data/day7");
+    fn part_1(&self, input: &str) -> Self::Result {
         determine_total_winnings(input, false)
     }
 
-    fn part_2(&self) -> Self::Result {
-        let input = include_str!("data/day7");
+    fn part_2(&self, input: &str) -> Self::Result {
         determine_total_winnings(input, true)
     }
+
+    fn explain(&self, part: u8, input: &str) -> Option<String> {
+        let hands = rank_hands(input, part == 2);
+        let mut lines = vec!["Rank  Hand   Kind             Bid  Winnings".to_string()];
+        for (index, (hand, labels)) in hands.iter().enumerate() {
+            let rank = index + 1;
+            lines.push(format!(
+                "{rank:>4}  {labels:<5}  {:<15}  {:>4}  {:>8}",
+                HAND_KIND_NAMES[hand.hand_kind_strength],
+                hand.bid,
+                rank * hand.bid
+            ));
+        }
+        Some(lines.join("\n"))
+    }
+
+    /// Swaps card labels for others throughout, which keeps every hand's
+    /// kind, then shuffles the cards in each hand, the hands and their bids.
+    /// Jokers stay jokers.
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        let labels = ['A', 'K', 'Q', 'T', '9', '8', '7', '6', '5', '4', '3', '2'];
+        let mut new_labels = labels;
+        rng.shuffle(&mut new_labels);
+
+        let mut hands = vec![];
+        for line in input.lines() {
+            let (hand, _) = line.split_once(' ')?;
+            // The cards keep their order, since reordering them could turn
+            // two different hands into the same one
+            let hand: String = hand
+                .chars()
+                .map(|ch| match labels.iter().position(|&label| label == ch) {
+                    Some(index) => new_labels[index],
+                    None => ch,
+                })
+                .collect();
+            hands.push(format!("{hand} {}\n", rng.range(1..=1000)));
+        }
+        rng.shuffle(&mut hands);
+        Some(hands.concat())
+    }
 }
 
+// Indexed by hand kind strength
+const HAND_KIND_NAMES: [&str; 7] = [
+    "high card",
+    "one pair",
+    "two pair",
+    "three of a kind",
+    "full house",
+    "four of a kind",
+    "five of a kind",
+];
+
 enum HandKind {
     FiveOfAKind,
     FourOfAKind,
@@ -127,36 +178,43 @@ impl Eq for Hand {}
 
 impl PartialOrd for Hand {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         if self.hand_kind_strength.eq(&other.hand_kind_strength) {
             for i in 0..5 {
                 if self.card_strengths[i].ne(&other.card_strengths[i]) {
-                    return self.card_strengths[i].partial_cmp(&other.card_strengths[i]);
+                    return self.card_strengths[i].cmp(&other.card_strengths[i]);
                 }
             }
-            Some(std::cmp::Ordering::Equal)
+            std::cmp::Ordering::Equal
         } else {
-            self.hand_kind_strength
-                .partial_cmp(&other.hand_kind_strength)
+            self.hand_kind_strength.cmp(&other.hand_kind_strength)
         }
     }
 }
 
-impl Ord for Hand {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
-    }
-}
-
-fn determine_total_winnings(input: &str, jokers_enabled: bool) -> usize {
-    let mut hands: Vec<Hand> = input
+/// Returns each hand along with its labels, from the lowest rank to the highest.
+fn rank_hands(input: &str, jokers_enabled: bool) -> Vec<(Hand, &str)> {
+    let mut hands: Vec<(Hand, &str)> = input
         .lines()
-        .map(|line| Hand::from_str(line, jokers_enabled))
+        .map(|line| {
+            let labels = line.split_once(' ').map_or(line, |(labels, _)| labels);
+            (Hand::from_str(line, jokers_enabled), labels)
+        })
         .collect();
-    hands.sort_unstable();
+    hands.sort_unstable_by(|a, b| a.0.cmp(&b.0));
     hands
+}
+
+fn determine_total_winnings(input: &str, jokers_enabled: bool) -> usize {
+    rank_hands(input, jokers_enabled)
         .iter()
         .enumerate()
-        .map(|(index, hand)| {
+        .map(|(index, (hand, _))| {
             let rank = index + 1;
             rank * hand.bid
         })
@@ -166,6 +224,7 @@ fn determine_total_winnings(input: &str, jokers_enabled: bool) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input;
 
     const INPUT: &str = "32T3K 765
 T55J5 684
@@ -189,4 +248,21 @@ QQQJA 483";
         assert!(Hand::from_str("AAAJJ 1", true) > Hand::from_str("AAJJJ 1", true));
         assert!(Hand::from_str("AAJJJ 1", true) > Hand::from_str("AJJJJ 1", true));
     }
+
+    #[test]
+    fn explain() {
+        let explanation = Day7::new().explain(2, INPUT).unwrap();
+        let lines: Vec<&str> = explanation.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[1], "   1  32T3K  one pair          765       765");
+        assert_eq!(lines[5], "   5  KTJJT  four of a kind    220      1100");
+    }
+
+    #[test]
+    fn messy_input() {
+        for (mess, input) in input::messy_copies(INPUT) {
+            assert_eq!(determine_total_winnings(&input, false), 6440, "{mess}");
+            assert_eq!(determine_total_winnings(&input, true), 5905, "{mess}");
+        }
+    }
 }
@@ -1,31 +1,91 @@
 use core::panic;
 use std::collections::HashMap;
 
-use crate::Solution;
+use crate::anonymize::{Labels, Rng};
+use crate::dot::Graph;
+use crate::{checked, input, trace, Solution};
 
 #[derive(Default)]
 pub struct Day8 {}
 
 impl Solution for Day8 {
-    type Result = usize;
+    type Result = u64;
 
-    fn part_1(&self) -> Self::Result {
-        let input = include_str!("data/day8");
+    fn part_1(&self, input: &str) -> Self::Result {
         let (instructions, nodes) = parse(input);
         count_steps_once(instructions, &nodes)
     }
 
-    fn part_2(&self) -> Self::Result {
-        let input = include_str!("data/day8");
+    fn part_2(&self, input: &str) -> Self::Result {
         let (instructions, nodes) = parse(input);
         count_steps_simultanenous(instructions, &nodes)
     }
+
+    fn graph(&self, input: &str) -> Option<Graph> {
+        let (_, nodes) = parse(input);
+        let mut names: Vec<&&str> = nodes.keys().collect();
+        names.sort();
+
+        let mut graph = Graph::new();
+        for name in &names {
+            let shape = match name.chars().last() {
+                Some('A') => "box",
+                Some('Z') => "doublecircle",
+                _ => "ellipse",
+            };
+            graph.node(name, shape, None);
+        }
+        for name in names {
+            let (left, right) = nodes[name];
+            if left == right {
+                graph.edge(name, left, Some("L/R"));
+            } else {
+                graph.edge(name, left, Some("L"));
+                graph.edge(name, right, Some("R"));
+            }
+        }
+        Some(graph)
+    }
+
+    /// Renames nodes, keeping whether each one is a start or an end node, and
+    /// shuffles their order. The instructions are left as they are.
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        let mut sections = input::sections(input);
+        let instructions = sections.next()?;
+        let mut labels = Labels::new(&["AAA", "ZZZ"]);
+        let mut rename = |label: &str, rng: &mut Rng| {
+            labels.get(label, rng, |label, rng| {
+                let mut new_label = rng.word(2).to_ascii_uppercase();
+                new_label.push(match label.chars().last() {
+                    Some(end @ ('A' | 'Z')) => end,
+                    _ => *rng.pick(&['B', 'C', 'D', 'E', 'F', 'G', 'H', 'K', 'M', 'N', 'P']),
+                });
+                new_label
+            })
+        };
+
+        let mut lines = vec![];
+        for node_str in sections.next()?.lines() {
+            let (node_name, rest) = node_str.split_once(" = ")?;
+            let (left, right) = rest.get(1..rest.len() - 1)?.split_once(", ")?;
+            lines.push(format!(
+                "{} = ({}, {})\n",
+                rename(node_name, rng),
+                rename(left, rng),
+                rename(right, rng)
+            ));
+        }
+        rng.shuffle(&mut lines);
+        Some(format!("{instructions}\n\n{}", lines.concat()))
+    }
 }
 
 type Nodes<'a> = HashMap<&'a str, (&'a str, &'a str)>;
 
-fn parse(input: &str) -> (&str, Nodes) {
-    let (instructions, nodes_str) = input.split_once("\n\n").unwrap();
+fn parse(input: &str) -> (&str, Nodes<'_>) {
+    let mut sections = input::sections(input);
+    let instructions = sections.next().unwrap();
+    let nodes_str = sections.next().unwrap();
     let mut nodes = HashMap::new();
     for node_str in nodes_str.lines() {
         let (node_name, rest) = node_str.split_once(" = ").unwrap();
@@ -35,12 +95,7 @@ fn parse(input: &str) -> (&str, Nodes) {
     (instructions, nodes)
 }
 
-fn count_steps<F>(
-    instructions: &str,
-    nodes: &Nodes,
-    start_node: &str,
-    mut end_condition: F,
-) -> usize
+fn count_steps<F>(instructions: &str, nodes: &Nodes, start_node: &str, mut end_condition: F) -> u64
 where
     F: FnMut(&str) -> bool,
 {
@@ -61,18 +116,21 @@ where
     steps
 }
 
-fn count_steps_once(instructions: &str, nodes: &Nodes) -> usize {
+fn count_steps_once(instructions: &str, nodes: &Nodes) -> u64 {
     count_steps(instructions, nodes, "AAA", |node| node == "ZZZ")
 }
 
-fn count_steps_simultanenous(instructions: &str, nodes: &Nodes) -> usize {
+fn count_steps_simultanenous(instructions: &str, nodes: &Nodes) -> u64 {
     let start_nodes = nodes.keys().filter(|k| k.ends_with('A'));
-    let steps_per_node =
-        start_nodes.map(|node| count_steps(instructions, &nodes, node, |node| node.ends_with('Z')));
+    let steps_per_node = start_nodes.map(|node| {
+        let steps = count_steps(instructions, nodes, node, |node| node.ends_with('Z'));
+        trace::debug!("{node} reaches an end node after {steps} steps");
+        steps
+    });
     steps_per_node.fold(1, lcm)
 }
 
-fn gcd(a: usize, b: usize) -> usize {
+fn gcd(a: u64, b: u64) -> u64 {
     let mut a = a;
     let mut b = b;
     while b > 0 {
@@ -83,17 +141,16 @@ fn gcd(a: usize, b: usize) -> usize {
     a
 }
 
-fn lcm(a: usize, b: usize) -> usize {
-    a * b / gcd(a, b)
+fn lcm(a: u64, b: u64) -> u64 {
+    // Dividing first keeps the intermediate value as small as the result
+    checked::mul(a / gcd(a, b), b)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn part_1() {
-        let input_1 = "RL
+    const INPUT_1: &str = "RL
 
 AAA = (BBB, CCC)
 BBB = (DDD, EEE)
@@ -103,21 +160,13 @@ EEE = (EEE, EEE)
 GGG = (GGG, GGG)
 ZZZ = (ZZZ, ZZZ)";
 
-        let input_2 = "LLR
+    const INPUT_2: &str = "LLR
 
 AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)";
 
-        let (instructions, nodes) = parse(input_1);
-        assert_eq!(count_steps_once(instructions, &nodes), 2);
-        let (instructions, nodes) = parse(input_2);
-        assert_eq!(count_steps_once(instructions, &nodes), 6);
-    }
-
-    #[test]
-    fn part_2() {
-        let input = "LR
+    const INPUT_3: &str = "LR
 
 11A = (11B, XXX)
 11B = (XXX, 11Z)
@@ -128,7 +177,29 @@ ZZZ = (ZZZ, ZZZ)";
 22Z = (22B, 22B)
 XXX = (XXX, XXX)";
 
-        let (instructions, nodes) = parse(input);
+    #[test]
+    fn part_1() {
+        let (instructions, nodes) = parse(INPUT_1);
+        assert_eq!(count_steps_once(instructions, &nodes), 2);
+        let (instructions, nodes) = parse(INPUT_2);
+        assert_eq!(count_steps_once(instructions, &nodes), 6);
+    }
+
+    #[test]
+    fn part_2() {
+        let (instructions, nodes) = parse(INPUT_3);
         assert_eq!(count_steps_simultanenous(instructions, &nodes), 6);
     }
+
+    #[test]
+    fn messy_input() {
+        for (mess, input) in input::messy_copies(INPUT_1) {
+            let (instructions, nodes) = parse(&input);
+            assert_eq!(count_steps_once(instructions, &nodes), 2, "{mess}");
+        }
+        for (mess, input) in input::messy_copies(INPUT_3) {
+            let (instructions, nodes) = parse(&input);
+            assert_eq!(count_steps_simultanenous(instructions, &nodes), 6, "{mess}");
+        }
+    }
 }
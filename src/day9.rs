@@ -1,3 +1,4 @@
+use crate::anonymize::Rng;
 use crate::Solution;
 
 #[derive(Default)]
@@ -6,15 +7,31 @@ pub struct Day9 {}
 impl Solution for Day9 {
     type Result = i64;
 
-    fn part_1(&self) -> Self::Result {
-        let input = include_str!("data/day9");
+    fn part_1(&self, input: &str) -> Self::Result {
         sum_next_values(input, false)
     }
 
-    fn part_2(&self) -> Self::Result {
-        let input = include_str!("data/day9");
+    fn part_2(&self, input: &str) -> Self::Result {
         sum_next_values(input, true)
     }
+
+    /// Adds a random straight line to each sequence, which only changes its
+    /// first two levels of differences, so it stays just as predictable.
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        let mut lines = vec![];
+        for line in input.lines() {
+            let offset = rng.range(0..=100) as i64 - 50;
+            let slope = rng.range(0..=20) as i64 - 10;
+            let mut sequence = vec![];
+            for (i, n) in line.split_ascii_whitespace().enumerate() {
+                let n: i64 = n.parse().ok()?;
+                sequence.push((n + offset + slope * i as i64).to_string());
+            }
+            lines.push(sequence.join(" ") + "\n");
+        }
+        rng.shuffle(&mut lines);
+        Some(lines.concat())
+    }
 }
 
 fn sum_next_values(input: &str, reverse: bool) -> i64 {
@@ -50,6 +67,7 @@ fn extrapolate_next_value(sequence: Vec<i64>) -> i64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input;
 
     const INPUT: &str = "0 3 6 9 12 15
 1 3 6 10 15 21
@@ -64,4 +82,12 @@ mod tests {
     fn part_2() {
         assert_eq!(sum_next_values(INPUT, true), 2);
     }
+
+    #[test]
+    fn messy_input() {
+        for (mess, input) in input::messy_copies(INPUT) {
+            assert_eq!(sum_next_values(&input, false), 114, "{mess}");
+            assert_eq!(sum_next_values(&input, true), 2, "{mess}");
+        }
+    }
 }
@@ -0,0 +1,79 @@
+use std::fmt;
+
+/// A directed graph that can be written in Graphviz's DOT language, e.g. to
+/// render with `dot -Tsvg`.
+#[derive(Debug, Default)]
+pub struct Graph {
+    nodes: Vec<(String, &'static str, Option<String>)>,
+    edges: Vec<(String, String, Option<String>)>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a node with a Graphviz shape such as `box` or `diamond`, and a
+    /// label to show in place of its id.
+    pub fn node(&mut self, id: &str, shape: &'static str, label: Option<&str>) {
+        self.nodes
+            .push((id.to_string(), shape, label.map(str::to_string)));
+    }
+
+    pub fn edge(&mut self, from: &str, to: &str, label: Option<&str>) {
+        self.edges
+            .push((from.to_string(), to.to_string(), label.map(str::to_string)));
+    }
+}
+
+impl fmt::Display for Graph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "digraph {{")?;
+        writeln!(f, "    rankdir=LR;")?;
+        for (id, shape, label) in &self.nodes {
+            write!(f, "    {} [shape={shape}", quote(id))?;
+            if let Some(label) = label {
+                write!(f, ", label={}", quote(label))?;
+            }
+            writeln!(f, "];")?;
+        }
+        for (from, to, label) in &self.edges {
+            write!(f, "    {} -> {}", quote(from), quote(to))?;
+            if let Some(label) = label {
+                write!(f, " [label={}]", quote(label))?;
+            }
+            writeln!(f, ";")?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let mut graph = Graph::new();
+        graph.node("in", "box", Some("%in"));
+        graph.node("out", "doublecircle", None);
+        graph.edge("in", "out", Some("a\"b"));
+        graph.edge("out", "in", None);
+
+        assert_eq!(
+            graph.to_string(),
+            r#"digraph {
+    rankdir=LR;
+    "in" [shape=box, label="%in"];
+    "out" [shape=doublecircle];
+    "in" -> "out" [label="a\"b"];
+    "out" -> "in";
+}
+"#
+        );
+    }
+}
@@ -6,11 +6,11 @@ pub struct Example {}
 impl Solution for Example {
     type Result = usize;
 
-    fn part_1(&self) -> Self::Result {
+    fn part_1(&self, _input: &str) -> Self::Result {
         2023
     }
 
-    fn part_2(&self) -> Self::Result {
+    fn part_2(&self, _input: &str) -> Self::Result {
         2023 * 25
     }
 }
@@ -21,6 +21,6 @@ mod tests {
 
     #[test]
     fn it_works() {
-        assert_eq!(Example::new().part_1(), 2023);
+        assert_eq!(Example::new().part_1(""), 2023);
     }
 }
@@ -0,0 +1,269 @@
+/// A piece of an HTML document. Only what's needed to read Advent of Code
+/// pages is supported: tags, attributes, text and the common character
+/// references. Comments, doctypes and the contents of `<script>` and
+/// `<style>` elements are skipped.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    StartTag {
+        name: String,
+        attributes: Vec<(String, String)>,
+        self_closing: bool,
+    },
+    EndTag {
+        name: String,
+    },
+    Text(String),
+}
+
+impl Token {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        match self {
+            Self::StartTag { attributes, .. } => attributes
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str()),
+            _ => None,
+        }
+    }
+}
+
+/// Splits an HTML document into tokens. Malformed markup is kept as text
+/// rather than rejected, like browsers do.
+pub fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_text(&mut tokens, rest);
+            break;
+        };
+        push_text(&mut tokens, &rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+        } else if let Some((token, after)) = parse_tag(rest) {
+            rest = after;
+            if let Token::StartTag {
+                name,
+                self_closing: false,
+                ..
+            } = &token
+            {
+                if name == "script" || name == "style" {
+                    let close = format!("</{name}");
+                    let end = rest.to_ascii_lowercase().find(&close).unwrap_or(rest.len());
+                    rest = &rest[end..];
+                }
+            }
+            tokens.push(token);
+        } else {
+            push_text(&mut tokens, "<");
+            rest = &rest[1..];
+        }
+    }
+
+    tokens
+}
+
+fn push_text(tokens: &mut Vec<Token>, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    let text = decode(text);
+    match tokens.last_mut() {
+        Some(Token::Text(previous)) => previous.push_str(&text),
+        _ => tokens.push(Token::Text(text)),
+    }
+}
+
+/// Parses the tag at the start of `s`, returning it and the rest of `s`.
+fn parse_tag(s: &str) -> Option<(Token, &str)> {
+    let (is_end, s) = match s.strip_prefix("</") {
+        Some(s) => (true, s),
+        None => (false, &s[1..]),
+    };
+    if !s.starts_with(|ch: char| ch.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let name_end = s
+        .find(|ch: char| ch.is_whitespace() || ch == '/' || ch == '>')
+        .unwrap_or(s.len());
+    let name = s[..name_end].to_ascii_lowercase();
+    let mut rest = &s[name_end..];
+
+    let mut attributes = vec![];
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix("/>") {
+            let token = if is_end {
+                Token::EndTag { name }
+            } else {
+                Token::StartTag {
+                    name,
+                    attributes,
+                    self_closing: true,
+                }
+            };
+            return Some((token, after));
+        }
+        if let Some(after) = rest.strip_prefix('>') {
+            let token = if is_end {
+                Token::EndTag { name }
+            } else {
+                Token::StartTag {
+                    name,
+                    attributes,
+                    self_closing: false,
+                }
+            };
+            return Some((token, after));
+        }
+        if rest.is_empty() {
+            return None;
+        }
+
+        let key_end = rest
+            .find(|ch: char| ch.is_whitespace() || ch == '=' || ch == '>' || ch == '/')
+            .unwrap_or(rest.len())
+            .max(1);
+        let key = rest[..key_end].to_ascii_lowercase();
+        rest = rest[key_end..].trim_start();
+
+        let value = match rest.strip_prefix('=') {
+            Some(after) => {
+                let after = after.trim_start();
+                let (value, after) = match after.chars().next() {
+                    Some(quote @ ('"' | '\'')) => {
+                        let end = after[1..].find(quote)? + 1;
+                        (&after[1..end], &after[end + 1..])
+                    }
+                    _ => {
+                        let end = after
+                            .find(|ch: char| ch.is_whitespace() || ch == '>')
+                            .unwrap_or(after.len());
+                        after.split_at(end)
+                    }
+                };
+                rest = after;
+                decode(value)
+            }
+            None => String::new(),
+        };
+        attributes.push((key, value));
+    }
+}
+
+/// Replaces character references such as `&lt;` and `&#39;` with the
+/// characters they stand for. Unknown references are left as they are.
+pub fn decode(s: &str) -> String {
+    let mut decoded = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let reference = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| Some((decode_reference(&rest[1..end + 1])?, end + 2)));
+        match reference {
+            Some((ch, len)) => {
+                decoded.push(ch);
+                rest = &rest[len..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_reference(name: &str) -> Option<char> {
+    if let Some(number) = name.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+    let ch = match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "ndash" => '–',
+        "mdash" => '—',
+        "hellip" => '…',
+        "rarr" => '→',
+        "larr" => '←',
+        "times" => '×',
+        _ => return None,
+    };
+    Some(ch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start(name: &str, attributes: &[(&str, &str)]) -> Token {
+        Token::StartTag {
+            name: name.to_string(),
+            attributes: attributes
+                .iter()
+                .map(|&(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+            self_closing: false,
+        }
+    }
+
+    fn end(name: &str) -> Token {
+        Token::EndTag {
+            name: name.to_string(),
+        }
+    }
+
+    fn text(text: &str) -> Token {
+        Token::Text(text.to_string())
+    }
+
+    #[test]
+    fn it_works() {
+        let html = "<!DOCTYPE html>\n<!-- a <b>comment</b> -->\
+            <P class=\"day-desc\" id=x hidden>1 &lt; 2 &amp;&amp; 3&#62;2 &#x2014; a < b</P>\
+            <script>if (a < b) {}</script><br/><a href='/2023/day/1?x=1&amp;y=2'>link</a> &bogus;";
+        let tokens = tokenize(html);
+        assert_eq!(
+            tokens,
+            vec![
+                text("\n"),
+                start("p", &[("class", "day-desc"), ("id", "x"), ("hidden", "")]),
+                text("1 < 2 && 3>2 — a < b"),
+                end("p"),
+                start("script", &[]),
+                end("script"),
+                Token::StartTag {
+                    name: "br".to_string(),
+                    attributes: vec![],
+                    self_closing: true,
+                },
+                start("a", &[("href", "/2023/day/1?x=1&y=2")]),
+                text("link"),
+                end("a"),
+                text(" &bogus;"),
+            ]
+        );
+        assert_eq!(tokens[1].attribute("id"), Some("x"));
+        assert_eq!(tokens[1].attribute("title"), None);
+    }
+}
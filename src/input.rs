@@ -0,0 +1,118 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::vault;
+
+static DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// The directory puzzle inputs are read from, one `day<N>` file per day.
+/// This is `src/data` unless another directory was set.
+pub fn dir() -> PathBuf {
+    DIR.get()
+        .cloned()
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("src/data"))
+}
+
+/// Reads inputs from another directory for the rest of the run. Only the
+/// first directory set takes effect.
+pub fn set_dir(dir: PathBuf) {
+    let _ = DIR.set(dir);
+}
+
+pub fn path(day: u8) -> PathBuf {
+    dir().join(format!("day{day}"))
+}
+
+/// Reads a day's puzzle input at runtime, so a missing input only affects
+/// the day that needs it. Without a plain input, the day's encrypted input
+/// is decrypted instead, if there is one.
+pub fn read(day: u8) -> io::Result<String> {
+    match std::fs::read_to_string(path(day)) {
+        Err(error) if error.kind() == io::ErrorKind::NotFound && vault::path(day).exists() => {
+            vault::decrypt(day)
+        }
+        result => result,
+    }
+}
+
+/// Whether a day has an input, either plain or encrypted.
+pub fn exists(day: u8) -> bool {
+    path(day).exists() || vault::path(day).exists()
+}
+
+/// Normalizes line endings and whitespace, so that inputs saved with CRLF
+/// line endings, trailing whitespace or extra blank lines all parse the same
+/// way. The result always ends with a single newline.
+pub fn normalize(input: &str) -> String {
+    let lines: Vec<&str> = input.lines().map(str::trim_end).collect();
+    let start = lines.iter().position(|line| !line.is_empty());
+    let end = lines.iter().rposition(|line| !line.is_empty());
+    match (start, end) {
+        (Some(start), Some(end)) => lines[start..=end].join("\n") + "\n",
+        _ => String::new(),
+    }
+}
+
+/// Splits an input into its sections, which are separated by one or more
+/// blank lines.
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    let mut rest = input;
+    std::iter::from_fn(move || {
+        let line_end =
+            |s: &str, start: usize| s[start..].find('\n').map_or(s.len(), |i| start + i + 1);
+
+        while !rest.is_empty() && rest[..line_end(rest, 0)].trim().is_empty() {
+            rest = &rest[line_end(rest, 0)..];
+        }
+        if rest.is_empty() {
+            return None;
+        }
+
+        let mut end = 0;
+        while end < rest.len() && !rest[end..line_end(rest, end)].trim().is_empty() {
+            end = line_end(rest, end);
+        }
+
+        let section = rest[..end].trim_end();
+        rest = &rest[end..];
+        Some(section)
+    })
+}
+
+/// Copies of an example as it might be saved, with CRLF line endings,
+/// trailing whitespace, no final newline or trailing blank lines. Each copy
+/// is normalized the way an input is before a solution parses it.
+#[cfg(test)]
+pub(crate) fn messy_copies(example: &str) -> [(&'static str, String); 4] {
+    let with_trailing_whitespace: String = example
+        .lines()
+        .map(|line| format!("{line}  \t\n"))
+        .collect();
+    [
+        ("CRLF line endings", example.replace('\n', "\r\n") + "\r\n"),
+        ("trailing whitespace", with_trailing_whitespace + "\n\n"),
+        ("no final newline", example.trim_end().to_string()),
+        ("trailing blank lines", format!("{example}\n\n\n")),
+    ]
+    .map(|(mess, copy)| (mess, normalize(&copy)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes() {
+        assert_eq!(normalize("\r\na b  \r\n\r\nc\t\r\n\r\n\n"), "a b\n\nc\n");
+        assert_eq!(normalize("a\nb"), "a\nb\n");
+        assert_eq!(normalize(" \n"), "");
+    }
+
+    #[test]
+    fn splits_sections() {
+        let split: Vec<_> = sections("\na\nb\n\n\nc \r\n \r\nd\r\n\n").collect();
+        assert_eq!(split, vec!["a\nb", "c", "d"]);
+        assert_eq!(sections("").count(), 0);
+    }
+}
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::io;
+
+use crate::{input, inspect_day, missing_solution};
+
+/// Facts about the shape of an input that don't depend on which day it's for.
+#[derive(Debug, PartialEq)]
+struct Facts {
+    lines: usize,
+    // The length in characters and the (1-based) number of the longest line
+    longest_line: (usize, usize),
+    // The width and height, if every line has the same length
+    grid: Option<(usize, usize)>,
+    sections: usize,
+    histogram: Vec<(char, usize)>,
+}
+
+impl Facts {
+    fn from_str(input: &str) -> Self {
+        let lengths: Vec<usize> = input.lines().map(|line| line.chars().count()).collect();
+
+        let longest_line = lengths
+            .iter()
+            .enumerate()
+            .fold((0, 0), |longest, (index, &length)| {
+                if length > longest.0 {
+                    (length, index + 1)
+                } else {
+                    longest
+                }
+            });
+
+        let grid = match lengths.first() {
+            Some(&width) if width > 0 && lengths.iter().all(|&length| length == width) => {
+                Some((width, lengths.len()))
+            }
+            _ => None,
+        };
+
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        for ch in input.chars().filter(|&ch| ch != '\n') {
+            *counts.entry(ch).or_default() += 1;
+        }
+        let mut histogram: Vec<_> = counts.into_iter().collect();
+        histogram.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        Self {
+            lines: lengths.len(),
+            longest_line,
+            grid,
+            sections: input::sections(input).count(),
+            histogram,
+        }
+    }
+}
+
+/// Prints structural facts about a day's input, followed by any checks the
+/// day's solution makes on its assumptions about the input.
+pub fn inspect(day: u8) -> io::Result<()> {
+    let path = input::path(day);
+    let input = input::read(day)?;
+    let facts = Facts::from_str(&input);
+
+    println!("Input for day {day} ({}):", path.display());
+    println!("  lines: {}", facts.lines);
+    println!(
+        "  longest line: {} characters (line {})",
+        facts.longest_line.0, facts.longest_line.1
+    );
+    match facts.grid {
+        Some((width, height)) => println!("  grid: {width} x {height}"),
+        None => println!("  grid: no (lines differ in length)"),
+    }
+    println!("  sections: {}", facts.sections);
+
+    let histogram: Vec<String> = facts
+        .histogram
+        .iter()
+        .map(|(ch, count)| format!("{ch:?} {count}"))
+        .collect();
+    println!("  characters: {}", histogram.join(", "));
+
+    match inspect_day(day, &input::normalize(&input)) {
+        Some(checks) if !checks.is_empty() => {
+            println!("\nChecks for day {day}:");
+            for check in checks {
+                println!("  {check}");
+            }
+        }
+        Some(_) => (),
+        None => println!("\n{}", missing_solution(day)),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let facts = Facts::from_str("#.#\n..#\n\n###\r\n");
+        assert_eq!(
+            facts,
+            Facts {
+                lines: 4,
+                longest_line: (3, 1),
+                grid: None,
+                sections: 2,
+                histogram: vec![('#', 6), ('.', 3), ('\r', 1)],
+            }
+        );
+
+        let facts = Facts::from_str("ab\ncd");
+        assert_eq!(facts.grid, Some((2, 2)));
+        assert_eq!(facts.longest_line, (2, 1));
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::calendar::{format_duration, format_utc, unlock_time};
+use crate::json::{self, Value};
+use crate::terminal::format_table;
+use crate::Format;
+
+/// A private leaderboard, as Advent of Code gives it in JSON.
+#[derive(Debug, PartialEq)]
+struct Leaderboard {
+    year: u16,
+    members: Vec<Member>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Member {
+    name: String,
+    local_score: u64,
+    stars: u64,
+    /// When the member got their latest star, as a Unix time
+    last_star: u64,
+    /// When the member got each part's star, as Unix times, by day
+    days: BTreeMap<u8, [Option<u64>; 2]>,
+}
+
+/// How long a member took to get a day's stars.
+#[derive(Debug, PartialEq)]
+struct SolveTimes {
+    /// Since the puzzle unlocked
+    part_1: Option<Duration>,
+    /// Since the puzzle unlocked
+    part_2: Option<Duration>,
+    /// From part 1's star to part 2's
+    delta: Option<Duration>,
+}
+
+impl Leaderboard {
+    fn parse(s: &str) -> Result<Self, String> {
+        let document = json::parse(s).map_err(|error| format!("invalid JSON {error}"))?;
+        let year = document
+            .get("event")
+            .and_then(Value::as_str)
+            .and_then(|event| event.parse().ok())
+            .ok_or("missing `event`")?;
+        let members = document
+            .get("members")
+            .and_then(Value::as_object)
+            .ok_or("missing `members`")?
+            .iter()
+            .map(|(id, member)| Member::parse(id, member).ok_or(format!("invalid member {id}")))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut leaderboard = Self { year, members };
+        leaderboard.members.sort_by(|a, b| {
+            (b.local_score, b.stars)
+                .cmp(&(a.local_score, a.stars))
+                .then(a.last_star.cmp(&b.last_star))
+        });
+        Ok(leaderboard)
+    }
+
+    fn solve_times(&self, day: u8, stars: [Option<u64>; 2]) -> SolveTimes {
+        let unlock = unlock_time(self.year, day)
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let since_unlock = |star: Option<u64>| star.map(|star| secs(star.saturating_sub(unlock)));
+        SolveTimes {
+            part_1: since_unlock(stars[0]),
+            part_2: since_unlock(stars[1]),
+            delta: stars[0]
+                .zip(stars[1])
+                .map(|(part_1, part_2)| secs(part_2.saturating_sub(part_1))),
+        }
+    }
+}
+
+impl Member {
+    fn parse(id: &str, member: &Value) -> Option<Self> {
+        // Members who hide their names show up like this on the site
+        let name = match member.get("name")? {
+            Value::String(name) => name.clone(),
+            _ => format!("(anonymous user #{id})"),
+        };
+
+        let mut days = BTreeMap::new();
+        for (day, parts) in member.get("completion_day_level")?.as_object()? {
+            let mut stars = [None; 2];
+            for (part, star) in parts.as_object()? {
+                let index = match part.as_str() {
+                    "1" => 0,
+                    "2" => 1,
+                    _ => return None,
+                };
+                stars[index] = Some(star.get("get_star_ts")?.as_u64()?);
+            }
+            days.insert(day.parse().ok()?, stars);
+        }
+
+        Some(Self {
+            name,
+            local_score: member.get("local_score")?.as_u64()?,
+            stars: member.get("stars")?.as_u64()?,
+            last_star: member.get("last_star_ts")?.as_u64()?,
+            days,
+        })
+    }
+}
+
+fn secs(seconds: u64) -> Duration {
+    Duration::from_secs(seconds)
+}
+
+/// Prints a private leaderboard's standings, followed by how long each
+/// member took to solve each day.
+pub fn show(json: &str, format: Format) -> Result<(), String> {
+    let leaderboard = Leaderboard::parse(json)?;
+    match format {
+        Format::Text => print!("{}", to_text(&leaderboard)),
+        Format::Json => println!("{}", to_json(&leaderboard)),
+    }
+    Ok(())
+}
+
+fn to_text(leaderboard: &Leaderboard) -> String {
+    let last_day = leaderboard
+        .members
+        .iter()
+        .filter_map(|member| member.days.keys().last())
+        .max()
+        .copied()
+        .unwrap_or(0);
+
+    let mut rows = vec![["Rank", "Score", "Stars", "Days", "Name"]
+        .map(str::to_string)
+        .to_vec()];
+    for (index, member) in leaderboard.members.iter().enumerate() {
+        let days: String = (1..=last_day)
+            .map(|day| match member.days.get(&day) {
+                Some([Some(_), Some(_)]) => '*',
+                Some(_) => '+',
+                None => '.',
+            })
+            .collect();
+        rows.push(vec![
+            (index + 1).to_string(),
+            member.local_score.to_string(),
+            member.stars.to_string(),
+            days,
+            member.name.clone(),
+        ]);
+    }
+    let mut text = format!(
+        "Advent of Code {} (* both stars, + only the first)\n\n",
+        leaderboard.year
+    );
+    text.push_str(&format_table(&rows));
+
+    for day in 1..=last_day {
+        let mut times: Vec<(&str, u64, SolveTimes)> = leaderboard
+            .members
+            .iter()
+            .filter_map(|member| {
+                let stars = *member.days.get(&day)?;
+                let last_star = stars.into_iter().flatten().max()?;
+                let times = leaderboard.solve_times(day, stars);
+                Some((member.name.as_str(), last_star, times))
+            })
+            .collect();
+        if times.is_empty() {
+            continue;
+        }
+        // Members without a star sort last
+        times.sort_by_key(|(_, _, times)| {
+            (
+                times.part_2.is_none(),
+                times.part_2,
+                times.part_1.is_none(),
+                times.part_1,
+            )
+        });
+
+        let format = |time: Option<Duration>| time.map_or("-".to_string(), format_duration);
+        let mut rows = vec![["Name", "Part 1", "Part 2", "Delta", "Last star (UTC)"]
+            .map(str::to_string)
+            .to_vec()];
+        for (name, last_star, times) in times {
+            rows.push(vec![
+                name.to_string(),
+                format(times.part_1),
+                format(times.part_2),
+                format(times.delta),
+                format_utc(last_star),
+            ]);
+        }
+        text.push_str(&format!("\nDay {day}, from when it unlocked:\n\n"));
+        text.push_str(&format_table(&rows));
+    }
+
+    text
+}
+
+fn to_json(leaderboard: &Leaderboard) -> Value {
+    let members = leaderboard
+        .members
+        .iter()
+        .enumerate()
+        .map(|(index, member)| {
+            let days = member.days.iter().map(|(&day, &stars)| {
+                let times = leaderboard.solve_times(day, stars);
+                let seconds = |time: Option<Duration>| time.map(|time| time.as_secs());
+                Value::object([
+                    ("day", day.into()),
+                    ("part1_ts", stars[0].into()),
+                    ("part2_ts", stars[1].into()),
+                    ("part1_seconds", seconds(times.part_1).into()),
+                    ("part2_seconds", seconds(times.part_2).into()),
+                    ("delta_seconds", seconds(times.delta).into()),
+                ])
+            });
+            Value::object([
+                ("rank", (index + 1).into()),
+                ("name", member.name.as_str().into()),
+                ("local_score", member.local_score.into()),
+                ("stars", member.stars.into()),
+                ("days", Value::Array(days.collect())),
+            ])
+        });
+    Value::object([
+        ("year", u32::from(leaderboard.year).into()),
+        ("members", Value::Array(members.collect())),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let leaderboard = Leaderboard::parse(include_str!("fixtures/leaderboard.json")).unwrap();
+        assert_eq!(leaderboard.year, 2023);
+        let names: Vec<&str> = leaderboard
+            .members
+            .iter()
+            .map(|member| member.name.as_str())
+            .collect();
+        assert_eq!(names, ["Ada", "(anonymous user #2002)", "Grace", "Linus"]);
+
+        // Day 2 unlocked at 1701493200
+        assert_eq!(
+            leaderboard.solve_times(2, leaderboard.members[0].days[&2]),
+            SolveTimes {
+                part_1: Some(secs(754)),
+                part_2: Some(secs(1_421)),
+                delta: Some(secs(667)),
+            }
+        );
+        assert_eq!(
+            leaderboard.solve_times(2, leaderboard.members[2].days[&2]),
+            SolveTimes {
+                part_1: Some(secs(90_061)),
+                part_2: None,
+                delta: None,
+            }
+        );
+
+        assert_eq!(
+            to_text(&leaderboard),
+            include_str!("fixtures/leaderboard.txt")
+        );
+        assert!(Leaderboard::parse(r#"{"event": "2023"}"#).is_err());
+    }
+}
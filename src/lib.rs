@@ -1,8 +1,46 @@
 #![allow(clippy::missing_panics_doc)]
 #![allow(clippy::must_use_candidate, clippy::must_use_unit)]
 
-use seq_macro::seq;
-use std::time::Instant;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub mod anonymize;
+pub mod answers;
+pub mod batch;
+pub mod bench;
+pub mod cache;
+pub mod calendar;
+pub mod checked;
+pub mod client;
+pub mod config;
+pub mod crypto;
+pub mod dot;
+pub mod grid;
+pub mod html;
+pub mod input;
+pub mod inspect;
+pub mod json;
+pub mod leaderboard;
+pub mod list;
+pub mod metrics;
+pub mod ocr;
+pub mod parallel;
+pub mod play;
+pub mod puzzle;
+pub mod record;
+pub mod scaffold;
+mod terminal;
+pub mod toml;
+pub mod trace;
+pub mod tui;
+pub mod vault;
+pub mod verify;
+pub mod visualize;
+pub mod wait;
+pub mod watch;
 
 macro_rules! time {
     ($e: expr) => {{
@@ -13,37 +51,439 @@ macro_rules! time {
     }};
 }
 
-seq!(N in 1..=20 {
-    mod example;
-    #(mod day~N;)*
+/// Declares the days that have a solution, each behind its own feature, and
+/// the functions that dispatch to them by day.
+macro_rules! days {
+    ($($day:literal => $module:ident::$solution:ident if feature = $feature:literal,)*) => {
+        mod example;
+        $(#[cfg(feature = $feature)] mod $module;)*
 
-    pub fn solve(day: Option<u8>, part: Option<u8>) {
-        let days = if let Some(day) = day {
-            vec![day]
-        } else {
-            vec![#(N,)*]
+        /// Days that have a solution in this build.
+        pub const DAYS: &[u8] = &[$(#[cfg(feature = $feature)] $day,)*];
+
+        /// Days that have a solution, but were left out of this build by
+        /// turning off their features.
+        pub const DISABLED_DAYS: &[u8] = &[$(#[cfg(not(feature = $feature))] $day,)*];
+
+        /// Runs one part of a day's solution against the given input, or returns
+        /// `None` if there is no solution for that day. The part fails with an
+        /// error when its answer can't be turned into text.
+        pub fn run_part(day: u8, part: u8, input: &str) -> Option<Result<PartResult, String>> {
+            trace::set_day(day);
+            match day {
+                0 => Some(example::Example::new().run_part(part, input)),
+                $(
+                    #[cfg(feature = $feature)]
+                    $day => Some($module::$solution::new().run_part(part, input)),
+                )*
+                _ => None,
+            }
+        }
+
+        /// Returns the version of a day's solution, or `None` if there is no
+        /// solution for that day.
+        pub fn version(day: u8) -> Option<u32> {
+            match day {
+                0 => Some(example::Example::VERSION),
+                $(
+                    #[cfg(feature = $feature)]
+                    $day => Some($module::$solution::VERSION),
+                )*
+                _ => None,
+            }
+        }
+
+        /// Identifies the code behind a day's solution by its version and a hash
+        /// of its source file, or returns `None` if there is no solution for that
+        /// day. Answers are cached under it, so editing a day's file is enough to
+        /// stop its old answers from being reused.
+        pub fn solution_hash(day: u8) -> Option<u64> {
+            let (version, source) = match day {
+                0 => (example::Example::VERSION, include_str!("example.rs")),
+                $(
+                    #[cfg(feature = $feature)]
+                    $day => ($module::$solution::VERSION, include_str!(concat!(stringify!($module), ".rs"))),
+                )*
+                _ => return None,
+            };
+            Some(cache::fnv1a(&format!("{version}\n{source}")))
+        }
+
+        /// Explains how a day's solution arrives at the answer to a part, or
+        /// returns `None` if the day has no explanation for it.
+        pub fn explain(day: u8, part: u8, input: &str) -> Option<String> {
+            trace::set_day(day);
+            let input = &input::normalize(input);
+            match day {
+                0 => example::Example::new().explain(part, input),
+                $(
+                    #[cfg(feature = $feature)]
+                    $day => $module::$solution::new().explain(part, input),
+                )*
+                _ => None,
+            }
+        }
+
+        /// Draws the structure behind a day's answer to a part, or returns `None`
+        /// if the day has nothing to draw for it.
+        pub fn visualize_part(day: u8, part: u8, input: &str) -> Option<visualize::Picture> {
+            trace::set_day(day);
+            let input = &input::normalize(input);
+            match day {
+                0 => example::Example::new().visualize(part, input),
+                $(
+                    #[cfg(feature = $feature)]
+                    $day => $module::$solution::new().visualize(part, input),
+                )*
+                _ => None,
+            }
+        }
+
+        /// Records the steps of a day's simulation for a part, which is empty if
+        /// the day has nothing to animate.
+        pub fn animate_part(day: u8, part: u8, input: &str) -> play::Animation {
+            trace::set_day(day);
+            let input = &input::normalize(input);
+            match day {
+                0 => example::Example::new().animate(part, input),
+                $(
+                    #[cfg(feature = $feature)]
+                    $day => $module::$solution::new().animate(part, input),
+                )*
+                _ => play::Animation::default(),
+            }
+        }
+
+        /// Returns the graph in a day's input, or `None` if the day has no graph
+        /// to show.
+        pub fn graph_day(day: u8, input: &str) -> Option<dot::Graph> {
+            trace::set_day(day);
+            let input = &input::normalize(input);
+            match day {
+                0 => example::Example::new().graph(input),
+                $(
+                    #[cfg(feature = $feature)]
+                    $day => $module::$solution::new().graph(input),
+                )*
+                _ => None,
+            }
+        }
+
+        /// Rewrites a day's input into one that can be shared, or returns `None`
+        /// if the day has no way to.
+        pub fn anonymize_day(day: u8, input: &str, rng: &mut anonymize::Rng) -> Option<String> {
+            trace::set_day(day);
+            let input = &input::normalize(input);
+            match day {
+                0 => example::Example::new().anonymize(input, rng),
+                $(
+                    #[cfg(feature = $feature)]
+                    $day => $module::$solution::new().anonymize(input, rng),
+                )*
+                _ => None,
+            }
+        }
+
+        /// Runs a day's checks on its assumptions about the input, or returns
+        /// `None` if there is no solution for that day.
+        pub fn inspect_day(day: u8, input: &str) -> Option<Vec<String>> {
+            trace::set_day(day);
+            match day {
+                0 => Some(example::Example::new().inspect(input)),
+                $(
+                    #[cfg(feature = $feature)]
+                    $day => Some($module::$solution::new().inspect(input)),
+                )*
+                _ => None,
+            }
+        }
+    };
+}
+
+days! {
+    1 => day1::Day1 if feature = "day1",
+    2 => day2::Day2 if feature = "day2",
+    3 => day3::Day3 if feature = "day3",
+    4 => day4::Day4 if feature = "day4",
+    5 => day5::Day5 if feature = "day5",
+    6 => day6::Day6 if feature = "day6",
+    7 => day7::Day7 if feature = "day7",
+    8 => day8::Day8 if feature = "day8",
+    9 => day9::Day9 if feature = "day9",
+    10 => day10::Day10 if feature = "day10",
+    11 => day11::Day11 if feature = "day11",
+    12 => day12::Day12 if feature = "day12",
+    13 => day13::Day13 if feature = "day13",
+    14 => day14::Day14 if feature = "day14",
+    15 => day15::Day15 if feature = "day15",
+    16 => day16::Day16 if feature = "day16",
+    17 => day17::Day17 if feature = "day17",
+    18 => day18::Day18 if feature = "day18",
+    19 => day19::Day19 if feature = "day19",
+    20 => day20::Day20 if feature = "day20",
+}
+
+/// Explains why a day has no solution to run, which is either because there
+/// isn't one yet or because its feature is turned off.
+pub fn missing_solution(day: u8) -> String {
+    if DISABLED_DAYS.contains(&day) {
+        format!("Day {day} is disabled in this build; turn on the `day{day}` feature to run it")
+    } else {
+        format!("No solution for day {day}")
+    }
+}
+
+/// Runs one part like [`run_part`], but turns panics, such as checked
+/// arithmetic overflowing, into errors too.
+pub fn try_run_part(day: u8, part: u8, input: &str) -> Result<PartResult, String> {
+    match panic::catch_unwind(AssertUnwindSafe(|| run_part(day, part, input))) {
+        Ok(Some(result)) => result,
+        Ok(None) => Err(missing_solution(day)),
+        Err(payload) => Err(payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panicked".to_string())),
+    }
+}
+
+/// Runs one part like [`try_run_part`], giving up once it has run for
+/// `timeout`. A part that times out is left running in the background.
+pub fn run_part_with_timeout(
+    day: u8,
+    part: u8,
+    input: &str,
+    timeout: Option<Duration>,
+) -> Result<PartResult, String> {
+    let Some(timeout) = timeout else {
+        return try_run_part(day, part, input);
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let input = input.to_string();
+    thread::spawn(move || {
+        let _ = sender.send(try_run_part(day, part, &input));
+    });
+    receiver
+        .recv_timeout(timeout)
+        .unwrap_or_else(|_| Err(format!("Timed out after {timeout:?}")))
+}
+
+/// How the runner reports its results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+/// How [`solve`] runs solutions and reports their results.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub format: Format,
+    /// Prints the solution's explanation of how each answer was reached,
+    /// when it has one.
+    pub explain: bool,
+    /// A directory to write pictures of the answers to.
+    pub visualize: Option<PathBuf>,
+    /// Reuses answers from earlier runs on the same input.
+    pub cache: bool,
+    /// How long each part may run before it is reported as failed.
+    pub timeout: Option<Duration>,
+}
+
+/// Runs the given parts of the given days, reporting the results as the
+/// options say. Returns whether every part ran without an error.
+pub fn solve(days: &[u8], parts: &[u8], options: &Options) -> bool {
+    let format = options.format;
+    let explain = options.explain;
+    let visualize = options.visualize.as_deref();
+
+    let mut results = vec![];
+    let mut errors = vec![];
+
+    // Checked runs are for catching overflow, which cached answers would hide
+    let mut cache = (options.cache && !checked::enabled()).then(cache::Cache::load);
+
+    let (_, duration) = time!(for &day in days {
+        if format == Format::Text {
+            println!("\nRunning solution for day {}...", day);
+        }
+
+        let Some(solution) = solution_hash(day) else {
+            let error = missing_solution(day);
+            if format == Format::Text {
+                println!("{error}");
+            }
+            errors.push((day, None, error));
+            continue;
         };
 
-        let (_, duration) = time!(
-            for day in days {
-                println!("\nRunning solution for day {}...", day);
-                match day {
-                    0 => example::Example::new().run(part),
-                    #(N => day~N::Day~N::new().run(part),)*
-                    _ => (),
+        let input = match input::read(day) {
+            Ok(input) => input,
+            Err(error) => {
+                let error = if error.kind() == std::io::ErrorKind::NotFound {
+                    format!("No input found at {}", input::path(day).display())
+                } else {
+                    format!("Failed to read input: {error}")
                 };
+                if format == Format::Text {
+                    println!("{error}");
+                }
+                errors.push((day, None, error));
+                continue;
             }
-        );
+        };
+        let normalized = input::normalize(&input);
+
+        for &part in parts {
+            let cached = cache
+                .as_ref()
+                .and_then(|cache| cache.get(day, part, solution, &normalized));
+            let result = cached.map_or_else(
+                || run_part_with_timeout(day, part, &input, options.timeout),
+                Ok,
+            );
+
+            match result {
+                Ok(result) => {
+                    if let Some(cache) = cache.as_mut().filter(|_| !result.cached) {
+                        cache.insert(day, part, solution, &normalized, &result);
+                    }
+
+                    let explanation = explain.then(|| self::explain(day, part, &input)).flatten();
+                    if format == Format::Text {
+                        println!("{result}");
+                        for line in explanation.iter().flat_map(|e| e.lines()) {
+                            println!("    {line}");
+                        }
+                    }
+                    results.push((day, result, explanation));
 
-        println!("\n{:?} elapsed.", duration);
+                    let picture = visualize.zip(visualize_part(day, part, &input));
+                    if let Some((dir, picture)) = picture {
+                        match picture.save(dir, &format!("day{day}-part{part}")) {
+                            Ok(paths) if format == Format::Text => {
+                                for path in paths {
+                                    println!("    Wrote {}", path.display());
+                                }
+                            }
+                            Ok(_) => (),
+                            Err(error) => {
+                                let error = format!("Failed to write picture: {error}");
+                                if format == Format::Text {
+                                    println!("    {error}");
+                                }
+                                errors.push((day, Some(part), error));
+                            }
+                        }
+                    }
+                }
+                Err(error) => {
+                    if format == Format::Text {
+                        println!("Part {part} failed: {error}");
+                    }
+                    errors.push((day, Some(part), error));
+                }
+            }
+        }
+    });
+
+    if let Some(Err(error)) = cache.map(|cache| cache.save()) {
+        eprintln!("Failed to save cache: {error}");
     }
-});
+
+    let succeeded = errors.is_empty();
+    match format {
+        Format::Text => println!("\n{:?} elapsed.", duration),
+        Format::Json => {
+            let results = results.into_iter().map(|(day, result, explanation)| {
+                let mut value = result.to_json(day);
+                if let (json::Value::Object(fields), Some(explanation)) = (&mut value, explanation)
+                {
+                    fields.push(("explanation".to_string(), explanation.into()));
+                }
+                value
+            });
+            let errors = errors.into_iter().map(|(day, part, error)| {
+                json::Value::object([
+                    ("day", day.into()),
+                    ("part", part.into()),
+                    ("error", error.into()),
+                ])
+            });
+            let output = json::Value::object([
+                ("results", json::Value::Array(results.collect())),
+                ("errors", json::Value::Array(errors.collect())),
+                ("duration_ns", duration.as_nanos().into()),
+            ]);
+            println!("{output}");
+        }
+    }
+    succeeded
+}
+
+/// The answer to one part of a solution, along with how long it took and
+/// any counters the solution recorded.
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: String,
+    pub duration: Duration,
+    pub counters: metrics::Counters,
+    /// Whether the answer came from the cache rather than a run.
+    pub cached: bool,
+}
+
+impl PartResult {
+    pub fn to_json(&self, day: u8) -> json::Value {
+        let counters = self
+            .counters
+            .iter()
+            .map(|&(name, value)| (name, value.into()));
+
+        json::Value::object([
+            ("day", day.into()),
+            ("part", self.part.into()),
+            ("answer", self.answer.as_str().into()),
+            ("duration_ns", self.duration.as_nanos().into()),
+            ("counters", json::Value::object(counters)),
+            ("cached", self.cached.into()),
+        ])
+    }
+}
+
+impl std::fmt::Display for PartResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cached = if self.cached { ", cached" } else { "" };
+        write!(
+            f,
+            "Part {} ({:?}{cached}): {}",
+            self.part, self.duration, self.answer
+        )?;
+        if !self.counters.is_empty() {
+            let counters: Vec<String> = self
+                .counters
+                .iter()
+                .map(|(name, value)| format!("{name}: {value}"))
+                .collect();
+            write!(f, " [{}]", counters.join(", "))?;
+        }
+        Ok(())
+    }
+}
 
 pub trait Solution {
     type Result;
 
-    fn part_1(&self) -> Self::Result;
-    fn part_2(&self) -> Self::Result;
+    /// Bumped whenever a change outside the day's own file, such as to a shared
+    /// module it uses, could change its answers, so that answers cached from
+    /// earlier versions aren't reused. Changes to the day's file are picked up
+    /// by [`solution_hash`] without a bump.
+    const VERSION: u32 = 1;
+
+    fn part_1(&self, input: &str) -> Self::Result;
+    fn part_2(&self, input: &str) -> Self::Result;
 
     fn new() -> Self
     where
@@ -52,18 +492,92 @@ pub trait Solution {
         Self::default()
     }
 
-    fn run(&self, part: Option<u8>)
+    /// Checks the assumptions the solution makes about its input, returning a
+    /// line for each fact worth knowing. Lines for assumptions that don't hold
+    /// start with "warning:".
+    fn inspect(&self, _input: &str) -> Vec<String> {
+        vec![]
+    }
+
+    /// Describes the intermediate structure behind the answer to a part, for
+    /// solutions that have one worth showing.
+    fn explain(&self, _part: u8, _input: &str) -> Option<String> {
+        None
+    }
+
+    /// Draws the answer to a part, such as a path through a grid, for
+    /// solutions where a picture helps.
+    fn visualize(&self, _part: u8, _input: &str) -> Option<visualize::Picture> {
+        None
+    }
+
+    /// Records the intermediate states of a simulation, for solutions that
+    /// step through one.
+    fn animate(&self, _part: u8, _input: &str) -> play::Animation {
+        play::Animation::default()
+    }
+
+    /// Builds the graph described by the input, for puzzles about one.
+    fn graph(&self, _input: &str) -> Option<dot::Graph> {
+        None
+    }
+
+    /// Rewrites an input into one with the same structure that can be shared
+    /// without giving away the original, such as by renaming labels, turning
+    /// grids and changing numbers. Whatever the solution relies on about its
+    /// input has to still hold.
+    fn anonymize(&self, _input: &str, _rng: &mut anonymize::Rng) -> Option<String> {
+        None
+    }
+
+    /// Runs one part, failing when its answer can't be turned into text.
+    fn run_part(&self, part: u8, input: &str) -> Result<PartResult, String>
     where
-        Self::Result: std::fmt::Display,
+        Self::Result: Answer,
     {
-        if part.is_none() || part.unwrap() == 1 {
-            let (result, duration) = time!(self.part_1());
-            println!("Part 1 ({:?}): {}", duration, result);
+        let input = &input::normalize(input);
+
+        metrics::take();
+        let (result, duration) = if part == 1 {
+            time!(self.part_1(input))
+        } else {
+            time!(self.part_2(input))
+        };
+
+        Ok(PartResult {
+            part,
+            answer: result.to_answer()?,
+            duration,
+            counters: metrics::take(),
+            cached: false,
+        })
+    }
+}
+
+/// A solution's answer to a part, as the text it's reported as. Numbers are
+/// written out as they are, and letters read with [`ocr::recognize`] fail
+/// the part when some of them couldn't be read.
+pub trait Answer {
+    fn to_answer(&self) -> Result<String, String>;
+}
+
+macro_rules! display_answers {
+    ($($t:ty),*) => {$(
+        impl Answer for $t {
+            fn to_answer(&self) -> Result<String, String> {
+                Ok(self.to_string())
+            }
         }
+    )*};
+}
+
+display_answers!(u32, u64, u128, usize, i32, i64, i128, isize, String);
 
-        if part.is_none() || part.unwrap() == 2 {
-            let (result, duration) = time!(self.part_2());
-            println!("Part 2 ({:?}): {}", duration, result);
+impl Answer for Result<String, ocr::OcrError> {
+    fn to_answer(&self) -> Result<String, String> {
+        match self {
+            Ok(text) => Ok(text.clone()),
+            Err(error) => Err(error.to_string()),
         }
     }
 }
@@ -0,0 +1,58 @@
+use crate::answers::Answers;
+use crate::terminal::format_table;
+use crate::{input, json, version, Format, DISABLED_DAYS};
+
+/// Lists every day of the month with whether it has a solution, an input
+/// and known answers. Days whose solution was left out of the build are
+/// listed as disabled.
+pub fn list(format: Format) {
+    let answers = Answers::load();
+    let days = (1..=25).map(|day| {
+        let known_answers: Vec<u8> = [1, 2]
+            .into_iter()
+            .filter(|&part| answers.get(day, part).is_some())
+            .collect();
+        (day, version(day), input::exists(day), known_answers)
+    });
+
+    match format {
+        Format::Text => {
+            let mut rows = vec![["Day", "Solution", "Input", "Answers"]
+                .map(str::to_string)
+                .to_vec()];
+            for (day, version, has_input, known_answers) in days {
+                let known_answers: Vec<String> = known_answers
+                    .iter()
+                    .map(|part| format!("part {part}"))
+                    .collect();
+                rows.push(vec![
+                    day.to_string(),
+                    match version {
+                        Some(version) => format!("v{version}"),
+                        None if DISABLED_DAYS.contains(&day) => "disabled".to_string(),
+                        None => "-".to_string(),
+                    },
+                    if has_input { "yes" } else { "-" }.to_string(),
+                    if known_answers.is_empty() {
+                        "-".to_string()
+                    } else {
+                        known_answers.join(", ")
+                    },
+                ]);
+            }
+            print!("{}", format_table(&rows));
+        }
+        Format::Json => {
+            let days = days.map(|(day, version, has_input, known_answers)| {
+                json::Value::object([
+                    ("day", day.into()),
+                    ("version", version.into()),
+                    ("disabled", DISABLED_DAYS.contains(&day).into()),
+                    ("has_input", has_input.into()),
+                    ("known_answers", known_answers.into()),
+                ])
+            });
+            println!("{}", json::Value::Array(days.collect()));
+        }
+    }
+}
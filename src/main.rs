@@ -1,36 +1,817 @@
-use clap::Parser;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use aoc::config::Config;
+use clap::{ArgAction, Parser, Subcommand};
 
 /// Solves Advent of Code 2023 problems
 #[derive(Parser, Debug)]
 #[command(author, about, long_about = None)]
 struct Args {
-    /// A specific day of the month (1 to 25)
+    /// What to do; runs every day when left out
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// `--day` from before there were subcommands, kept so that `aoc --day 3`
+    /// still runs day 3
+    #[arg(long = "day", hide = true, value_name = "DAY", value_parser = parse_days)]
+    legacy_day: Option<Days>,
+
+    /// `--part` from before there were subcommands
+    #[arg(long = "part", hide = true, value_name = "PART", value_parser = parse_part)]
+    legacy_part: Option<u8>,
+
+    /// The config file to read settings from (defaults to aoc.toml in the
+    /// project root). Flags override environment variables, which override
+    /// the config file
+    #[arg(long, global = true, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// The year of the puzzles to fetch and submit answers for (defaults to
+    /// 2023)
+    #[arg(long, global = true)]
+    year: Option<u16>,
+
+    /// The directory to read puzzle inputs from, one `day<N>` file per day
+    /// (defaults to src/data)
+    #[arg(long, global = true, value_name = "DIR")]
+    input_dir: Option<PathBuf>,
+
+    /// The file to read known answers from (defaults to `answers` in the
+    /// input directory)
+    #[arg(long, global = true, value_name = "FILE")]
+    answers: Option<PathBuf>,
+
+    /// How results are reported (defaults to text)
+    #[arg(long, global = true, value_enum)]
+    format: Option<aoc::Format>,
+
+    /// Reports arithmetic overflow in solutions as an error (always on when
+    /// built with the `checked-arithmetic` feature)
+    #[arg(long, global = true)]
+    checked: bool,
+
+    /// Shows solution traces; repeat for more detail (-v info, -vv debug,
+    /// -vvv trace). Traces can also be filtered per day with AOC_LOG, e.g.
+    /// AOC_LOG=20=trace
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// Writes traces to a file instead of stderr
+    #[arg(long, global = true)]
+    log_file: Option<PathBuf>,
+
+    /// How many threads solutions split their work over (defaults to one per
+    /// core)
+    #[arg(long, global = true)]
+    threads: Option<usize>,
+}
+
+/// Which days and parts to run.
+#[derive(clap::Args, Debug, Default)]
+struct Selection {
+    /// The days to run, as a day (3), an inclusive range (3..=7 or 3-7) or a
+    /// list of either (1,5,9..=11); defaults to every day with a solution
+    #[arg(long, value_parser = parse_days)]
+    day: Option<Days>,
+
+    /// The part of each solution to run (1 or 2); defaults to both
+    #[arg(long, value_parser = parse_part)]
+    part: Option<u8>,
+}
+
+impl Selection {
+    fn days(&self) -> Vec<u8> {
+        self.day
+            .as_ref()
+            .map_or_else(|| aoc::DAYS.to_vec(), |days| days.0.clone())
+    }
+
+    fn parts(&self) -> Vec<u8> {
+        self.part.map_or(vec![1, 2], |part| vec![part])
+    }
+}
+
+#[derive(clap::Args, Debug, Default)]
+struct RunArgs {
+    #[command(flatten)]
+    selection: Selection,
+
+    /// Prints how each answer was reached, for days that can explain it
     #[arg(long)]
-    day: Option<u8>,
+    explain: bool,
 
-    /// The part of a solution to run (1 or 2)
+    /// Writes PPM and SVG pictures of the answers to a directory, for days
+    /// that can draw them
+    #[arg(long, value_name = "DIR")]
+    visualize: Option<PathBuf>,
+
+    /// Runs every part instead of reusing answers cached from earlier runs
+    /// on the same input
     #[arg(long)]
-    part: Option<u8>,
+    no_cache: bool,
+
+    /// How many seconds each part may run before it is reported as failed
+    #[arg(long, value_name = "SECONDS")]
+    timeout: Option<f64>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Runs solutions and prints their answers
+    Run(RunArgs),
+
+    /// Runs solutions several times and reports how long they take
+    Bench {
+        #[command(flatten)]
+        selection: Selection,
+
+        /// How many times to run each part (defaults to 10)
+        #[arg(long)]
+        runs: Option<usize>,
+
+        /// How many untimed runs of each part come first (defaults to 1)
+        #[arg(long)]
+        warmup: Option<usize>,
+    },
+
+    /// Checks solutions against the known answers in the `answers` file,
+    /// failing if any answer is wrong
+    Verify {
+        #[command(flatten)]
+        selection: Selection,
+    },
+
+    /// Downloads puzzle inputs to the input directory, using the session
+    /// cookie in AOC_SESSION or the configured session file
+    Fetch {
+        /// The days to fetch, like `--day` for `run`
+        #[arg(long, value_parser = parse_days)]
+        day: Days,
+    },
+
+    /// Submits an answer to Advent of Code, using the session cookie in
+    /// AOC_SESSION or the configured session file
+    Submit {
+        /// The day of the month to submit an answer for (1 to 25)
+        #[arg(long, value_parser = parse_day)]
+        day: u8,
+
+        /// The part to submit an answer for (1 or 2)
+        #[arg(long, value_parser = parse_part)]
+        part: u8,
+
+        /// The answer to submit; defaults to the solution's answer
+        #[arg(long)]
+        answer: Option<String>,
+    },
+
+    /// Shows a day's puzzle description in the terminal, downloading it
+    /// unless it's cached
+    Read {
+        /// The day of the month to read (1 to 25)
+        #[arg(long, value_parser = parse_day)]
+        day: u8,
+
+        /// Reads a saved puzzle page instead
+        #[arg(long, value_name = "FILE", conflicts_with = "refresh")]
+        file: Option<PathBuf>,
+
+        /// Downloads the page again even if it's cached
+        #[arg(long)]
+        refresh: bool,
+    },
+
+    /// Shows a private leaderboard's standings and how long each member took
+    /// to solve each day
+    Leaderboard {
+        /// A leaderboard JSON file, the URL of one, or a private
+        /// leaderboard's id to download it from Advent of Code
+        source: String,
+    },
+
+    /// Waits for a day's puzzle to unlock at midnight US Eastern time, then
+    /// fetches its input and runs the day's tests
+    Wait {
+        /// The day of the month to wait for (1 to 25)
+        #[arg(long, value_parser = parse_day)]
+        day: u8,
+    },
+
+    /// Encrypts inputs into the encrypted input directory, which can be
+    /// committed. The runner decrypts them when there is no plain input
+    Encrypt {
+        /// The days to encrypt, like `--day` for `run`; defaults to every day
+        /// with a plain input
+        #[arg(long, value_parser = parse_days)]
+        day: Option<Days>,
+    },
+
+    /// Generates the key that inputs are encrypted with, which must be kept
+    /// out of the repository
+    Keygen,
+
+    /// Starts a new day's solution from a template
+    New {
+        /// The day of the month to start (1 to 25)
+        #[arg(long, value_parser = parse_day)]
+        day: u8,
+    },
+
+    /// Prints facts about days' inputs and checks the solutions' assumptions
+    /// about them
+    Inspect {
+        /// The days to inspect, like `--day` for `run`
+        #[arg(long, value_parser = parse_days)]
+        day: Days,
+    },
+
+    /// Lists every day with whether it has a solution, an input and known
+    /// answers
+    List,
+
+    /// Re-runs a day whenever its source or input file changes
+    Watch {
+        /// The day of the month to watch (1 to 25)
+        #[arg(long, value_parser = parse_day)]
+        day: u8,
+
+        /// The part of the solution to run (1 or 2)
+        #[arg(long, value_parser = parse_part)]
+        part: Option<u8>,
+    },
+
+    /// Runs a day against every input in a directory, comparing each with the
+    /// answers in a sidecar `<input>.answers` file when there is one
+    Batch {
+        /// The day of the month to run (1 to 25)
+        #[arg(long, value_parser = parse_day)]
+        day: u8,
+
+        /// The part of the solution to run (1 or 2)
+        #[arg(long, value_parser = parse_part)]
+        part: Option<u8>,
+
+        /// The directory holding the inputs
+        #[arg(long)]
+        inputs: PathBuf,
+    },
+
+    /// Plays back the steps of a day's simulation in the terminal
+    Play {
+        /// The day of the month to play (1 to 25)
+        #[arg(long, value_parser = parse_day)]
+        day: u8,
+
+        /// The part of the solution to play (1 or 2)
+        #[arg(long, value_parser = parse_part, default_value_t = 1)]
+        part: u8,
+    },
+
+    /// Prints the graph in a day's input in Graphviz's DOT language
+    Graph {
+        /// The day of the month to graph (1 to 25)
+        #[arg(long, value_parser = parse_day)]
+        day: u8,
+    },
+
+    /// Rewrites a day's input into an equivalent one that can be shared, and
+    /// checks that the solution still solves it
+    Anonymize {
+        /// The day of the month to anonymize (1 to 25)
+        #[arg(long, value_parser = parse_day)]
+        day: u8,
+
+        /// Picks the rewrite; the same seed always gives the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Writes the new input to a file instead of printing it
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Writes an HTML page for scrubbing through the states a day's solution
+    /// recorded while running
+    Visualize {
+        /// The day of the month to record (1 to 25)
+        #[arg(long, value_parser = parse_day)]
+        day: u8,
+
+        /// The part of the solution to record (1 or 2)
+        #[arg(long, value_parser = parse_part, default_value_t = 1)]
+        part: u8,
+
+        /// The HTML file to write
+        #[arg(long, value_name = "FILE")]
+        html: PathBuf,
+    },
+
+    /// Shows a live dashboard of every day's status and timings
+    Tui,
 }
 
-fn main() {
+/// Days given as a day, a range or a list, in order and without repeats.
+#[derive(Debug, Clone, PartialEq)]
+struct Days(Vec<u8>);
+
+fn parse_days(spec: &str) -> Result<Days, String> {
+    let mut days = vec![];
+    for item in spec.split(',') {
+        let item = item.trim();
+        let range = item.split_once("..=").or_else(|| item.split_once('-'));
+        if range.is_none() && item.contains("..") {
+            return Err(format!(
+                "{item} is ambiguous; write an inclusive range as {}",
+                item.replacen("..", "..=", 1)
+            ));
+        }
+        match range {
+            Some((first, last)) => {
+                let first = parse_day(first)?;
+                let last = parse_day(last)?;
+                if first > last {
+                    return Err(format!("{item} is an empty range of days"));
+                }
+                days.extend(first..=last);
+            }
+            None => days.push(parse_day(item)?),
+        }
+    }
+    days.sort_unstable();
+    days.dedup();
+    Ok(Days(days))
+}
+
+fn parse_day(s: &str) -> Result<u8, String> {
+    match s.trim().parse() {
+        Ok(day @ 1..=25) => Ok(day),
+        _ => Err(format!("{s:?} isn't a day between 1 and 25")),
+    }
+}
+
+fn parse_part(s: &str) -> Result<u8, String> {
+    match s.trim().parse() {
+        Ok(part @ (1 | 2)) => Ok(part),
+        _ => Err(format!("{s:?} isn't a part (1 or 2)")),
+    }
+}
+
+fn main() -> ExitCode {
     let args = Args::parse();
 
-    if let Some(day) = args.day {
-        if day > 25 {
-            panic!("argument 'day' should be between 1 and 25");
+    if args.checked {
+        aoc::checked::set_enabled(true);
+    }
+
+    if let Err(error) = aoc::trace::init(args.verbose, args.log_file.as_deref()) {
+        eprintln!("Failed to open log file: {error}");
+    }
+
+    let mut config = match Config::load(args.config.as_deref()) {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("Invalid config: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Some(year) = args.year {
+        config.year = year;
+    }
+    if let Some(dir) = args.input_dir {
+        config.input_dir = dir;
+    }
+    if let Some(file) = args.answers {
+        config.answers_file = Some(file);
+    }
+    if let Some(format) = args.format {
+        config.format = format;
+    }
+    if let Some(threads) = args.threads {
+        config.threads = threads;
+    }
+    aoc::input::set_dir(config.input_dir.clone());
+    aoc::answers::set_path(config.answers_file());
+    aoc::vault::set_dir(config.encrypted_dir.clone());
+    aoc::vault::set_key_path(config.key_file.clone());
+    aoc::parallel::set_threads(config.threads);
+
+    let format = config.format;
+    let legacy = Selection {
+        day: args.legacy_day,
+        part: args.legacy_part,
+    };
+    let command = match args.command {
+        None => Command::Run(RunArgs {
+            selection: legacy,
+            ..RunArgs::default()
+        }),
+        Some(_) if legacy.day.is_some() || legacy.part.is_some() => {
+            eprintln!("--day and --part go after the command, e.g. `aoc run --day 3`");
+            return ExitCode::FAILURE;
+        }
+        Some(command) => command,
+    };
+    let succeeded = match command {
+        Command::Run(run) => {
+            let timeout = match run.timeout {
+                Some(seconds) if seconds > 0.0 => Some(Duration::from_secs_f64(seconds)),
+                Some(_) => None,
+                None => config.timeout,
+            };
+            let options = aoc::Options {
+                format,
+                explain: run.explain,
+                visualize: run.visualize,
+                cache: !run.no_cache,
+                timeout,
+            };
+            aoc::solve(&run.selection.days(), &run.selection.parts(), &options)
+        }
+        Command::Bench {
+            selection,
+            runs,
+            warmup,
+        } => {
+            aoc::bench::bench(
+                &selection.days(),
+                &selection.parts(),
+                runs.unwrap_or(config.bench_runs),
+                warmup.unwrap_or(config.bench_warmup),
+                format,
+            );
+            true
+        }
+        Command::Verify { selection } => {
+            aoc::verify::verify(&selection.days(), &selection.parts(), format)
+        }
+        Command::Fetch { day } => fetch(&config, &day.0),
+        Command::Submit { day, part, answer } => submit(&config, day, part, answer),
+        Command::Read { day, file, refresh } => read(&config, day, file.as_deref(), refresh),
+        Command::Leaderboard { source } => leaderboard(&config, &source),
+        Command::Wait { day } => wait(&config, day),
+        Command::Encrypt { day } => {
+            let days = day.map_or_else(
+                || {
+                    (1..=25)
+                        .filter(|&day| aoc::input::path(day).exists())
+                        .collect()
+                },
+                |days| days.0,
+            );
+            encrypt(&days)
+        }
+        Command::Keygen => match aoc::vault::generate_key() {
+            Ok(path) => {
+                println!("Wrote a new key to {}", path.display());
+                true
+            }
+            Err(error) => {
+                eprintln!("Failed to write key: {error}");
+                false
+            }
+        },
+        Command::New { day } => match aoc::scaffold::new_day(day) {
+            Ok(path) => {
+                println!("Created {}", path.display());
+                true
+            }
+            Err(error) => {
+                eprintln!("Failed to add day {day}: {error}");
+                false
+            }
+        },
+        Command::Inspect { day } => day.0.iter().all(|&day| {
+            if let Err(error) = aoc::inspect::inspect(day) {
+                eprintln!("Failed to read input for day {day}: {error}");
+                return false;
+            }
+            true
+        }),
+        Command::List => {
+            aoc::list::list(format);
+            true
+        }
+        Command::Watch { day, part } => {
+            aoc::watch::watch(day, part, args.config.as_deref());
+            true
+        }
+        Command::Batch { day, part, inputs } => {
+            let parts = part.map_or(vec![1, 2], |part| vec![part]);
+            match aoc::batch::batch(day, &parts, &inputs) {
+                Ok(passed) => passed,
+                Err(error) => {
+                    eprintln!("Failed to read inputs from {}: {error}", inputs.display());
+                    false
+                }
+            }
+        }
+        Command::Play { day, part } => match aoc::input::read(day) {
+            Ok(input) => {
+                let animation = aoc::animate_part(day, part, &input);
+                let title = format!("Day {day} part {part}");
+                let result = aoc::play::play(&title, &animation);
+                if let Err(error) = &result {
+                    eprintln!("Failed to play animation: {error}");
+                }
+                result.is_ok()
+            }
+            Err(error) => {
+                eprintln!("Failed to read input for day {day}: {error}");
+                false
+            }
+        },
+        Command::Graph { day } => match aoc::input::read(day) {
+            Ok(input) => match aoc::graph_day(day, &input) {
+                Some(graph) => {
+                    print!("{graph}");
+                    true
+                }
+                None => {
+                    eprintln!("Day {day} has no graph to show");
+                    false
+                }
+            },
+            Err(error) => {
+                eprintln!("Failed to read input for day {day}: {error}");
+                false
+            }
+        },
+        Command::Anonymize { day, seed, output } => anonymize(day, seed, output.as_deref()),
+        Command::Visualize { day, part, html } => record(day, part, &html),
+        Command::Tui => {
+            let result = aoc::tui::run();
+            if let Err(error) = &result {
+                eprintln!("Failed to run dashboard: {error}");
+            }
+            result.is_ok()
+        }
+    };
+
+    if succeeded {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn fetch(config: &Config, days: &[u8]) -> bool {
+    let client = match aoc::client::Client::from_config(config).require_session() {
+        Ok(client) => client,
+        Err(error) => {
+            eprintln!("Can't fetch inputs: {error}");
+            return false;
+        }
+    };
+
+    let mut succeeded = true;
+    for &day in days {
+        match client.fetch_input(day) {
+            Ok(path) => println!("Day {day}: {}", path.display()),
+            Err(error) => {
+                eprintln!("Failed to fetch input for day {day}: {error}");
+                succeeded = false;
+            }
         }
     }
+    succeeded
+}
 
-    if let Some(part) = args.part {
-        if part != 1 && part != 2 {
-            panic!("argument 'part' should be 1 or 2");
+fn read(config: &Config, day: u8, file: Option<&Path>, refresh: bool) -> bool {
+    let page = match file {
+        Some(file) => fs::read_to_string(file)
+            .map_err(|error| format!("Failed to read {}: {error}", file.display())),
+        None => {
+            let client = aoc::client::Client::from_config(config)
+                .require_session()
+                .ok();
+            aoc::puzzle::load(client.as_ref(), day, refresh)
+                .map_err(|error| format!("Failed to download the puzzle for day {day}: {error}"))
+        }
+    };
+    let page = match page {
+        Ok(page) => page,
+        Err(error) => {
+            eprintln!("{error}");
+            return false;
         }
+    };
+
+    let articles = aoc::puzzle::articles(&page);
+    if articles.is_empty() {
+        eprintln!("The page for day {day} has no puzzle description");
+        return false;
     }
+    let styled = io::stdout().is_terminal();
+    let text: Vec<String> = articles
+        .iter()
+        .map(|article| aoc::puzzle::render(article, styled))
+        .collect();
+    print!("{}", text.join("\n"));
+    true
+}
 
-    if args.day.is_some() {
-        aoc::solve(args.day, args.part);
+fn leaderboard(config: &Config, source: &str) -> bool {
+    let json = if Path::new(source).exists() {
+        fs::read_to_string(source).map_err(|error| format!("Failed to read {source}: {error}"))
     } else {
-        aoc::solve(None, None);
+        aoc::client::Client::from_config(config)
+            .fetch_leaderboard(source)
+            .map_err(|error| format!("Failed to download leaderboard: {error}"))
+    };
+
+    let result = json.and_then(|json| {
+        aoc::leaderboard::show(&json, config.format)
+            .map_err(|error| format!("Invalid leaderboard: {error}"))
+    });
+    if let Err(error) = &result {
+        eprintln!("{error}");
+    }
+    result.is_ok()
+}
+
+fn wait(config: &Config, day: u8) -> bool {
+    // The session is checked up front rather than once the puzzle unlocks
+    let client = match aoc::client::Client::from_config(config).require_session() {
+        Ok(client) => client,
+        Err(error) => {
+            eprintln!("Can't fetch inputs: {error}");
+            return false;
+        }
+    };
+
+    match aoc::wait::wait(&client, config.year, day, &aoc::wait::SystemClock) {
+        Ok(passed) => passed,
+        Err(error) => {
+            eprintln!("Failed to wait for day {day}: {error}");
+            false
+        }
+    }
+}
+
+fn encrypt(days: &[u8]) -> bool {
+    let mut succeeded = true;
+    for &day in days {
+        match aoc::vault::encrypt(day) {
+            Ok(true) => println!("Day {day}: {}", aoc::vault::path(day).display()),
+            Ok(false) => println!("Day {day}: unchanged"),
+            Err(error) => {
+                eprintln!("Failed to encrypt input for day {day}: {error}");
+                succeeded = false;
+            }
+        }
+    }
+    succeeded
+}
+
+fn submit(config: &Config, day: u8, part: u8, answer: Option<String>) -> bool {
+    use aoc::client::{Client, Verdict};
+
+    let client = match Client::from_config(config).require_session() {
+        Ok(client) => client,
+        Err(error) => {
+            eprintln!("Can't submit answers: {error}");
+            return false;
+        }
+    };
+
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let result = aoc::input::read(day)
+                .map_err(|error| format!("Failed to read input for day {day}: {error}"))
+                .and_then(|input| aoc::try_run_part(day, part, &input));
+            match result {
+                Ok(result) => result.answer,
+                Err(error) => {
+                    eprintln!("{error}");
+                    return false;
+                }
+            }
+        }
+    };
+
+    println!("Submitting {answer} for day {day} part {part}...");
+    match client.submit(day, part, &answer) {
+        Ok(Verdict::Correct) => {
+            println!("That's the right answer!");
+            true
+        }
+        Ok(Verdict::Incorrect(hint)) => {
+            match hint {
+                Some(hint) => println!("That's not the right answer; {hint}."),
+                None => println!("That's not the right answer."),
+            }
+            false
+        }
+        Ok(Verdict::TooSoon(message) | Verdict::Unknown(message)) => {
+            println!("{message}");
+            false
+        }
+        Ok(Verdict::AlreadySolved) => {
+            println!("Day {day} part {part} is already solved.");
+            true
+        }
+        Err(error) => {
+            eprintln!("Failed to submit answer: {error}");
+            false
+        }
+    }
+}
+
+/// Anonymizes a day's input, printing the answers to it after the input or,
+/// when the input is written to a file, on their own.
+fn anonymize(day: u8, seed: u64, output: Option<&Path>) -> bool {
+    let input = match aoc::input::read(day) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("Failed to read input for day {day}: {error}");
+            return false;
+        }
+    };
+    let (anonymized, answers) = match aoc::anonymize::anonymize(day, &input, seed) {
+        Ok(result) => result,
+        Err(error) => {
+            eprintln!("Failed to anonymize day {day}: {error}");
+            return false;
+        }
+    };
+
+    match output {
+        Some(path) => {
+            if let Err(error) = fs::write(path, &anonymized) {
+                eprintln!("Failed to write {}: {error}", path.display());
+                return false;
+            }
+            println!("Wrote day {day}'s anonymized input to {}", path.display());
+            for (part, answer) in answers {
+                println!("Part {part}: {answer}");
+            }
+        }
+        // The answers go to stderr so that the input can be redirected
+        None => {
+            print!("{anonymized}");
+            for (part, answer) in answers {
+                eprintln!("Part {part}: {answer}");
+            }
+        }
+    }
+    true
+}
+
+/// Runs a part while recording its snapshots, writing them to an HTML page.
+fn record(day: u8, part: u8, html: &Path) -> bool {
+    let input = match aoc::input::read(day) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("Failed to read input for day {day}: {error}");
+            return false;
+        }
+    };
+
+    aoc::record::start();
+    let result = aoc::try_run_part(day, part, &input);
+    let snapshots = aoc::record::finish();
+    if let Err(error) = result {
+        eprintln!("Day {day} part {part} failed: {error}");
+        return false;
+    }
+    if snapshots.is_empty() {
+        eprintln!("Day {day} part {part} recorded nothing to visualize");
+        return false;
+    }
+
+    let title = format!("Day {day} part {part}");
+    match fs::write(html, aoc::record::to_html(&title, &snapshots)) {
+        Ok(()) => {
+            println!("Wrote {} snapshots to {}", snapshots.len(), html.display());
+            true
+        }
+        Err(error) => {
+            eprintln!("Failed to write {}: {error}", html.display());
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_days() {
+        assert_eq!(parse_days("3"), Ok(Days(vec![3])));
+        assert_eq!(parse_days("3..=7"), Ok(Days(vec![3, 4, 5, 6, 7])));
+        assert_eq!(parse_days("3-7"), Ok(Days(vec![3, 4, 5, 6, 7])));
+        assert_eq!(parse_days("9, 1,5..=6,5"), Ok(Days(vec![1, 5, 6, 9])));
+        assert!(parse_days("3..7").is_err());
+        assert!(parse_days("7..=3").is_err());
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("1,,2").is_err());
+        assert_eq!(parse_part("2"), Ok(2));
+        assert!(parse_part("3").is_err());
     }
 }
@@ -0,0 +1,43 @@
+use std::cell::RefCell;
+
+/// Named counters recorded by the solution running on the current thread.
+pub type Counters = Vec<(&'static str, u64)>;
+
+thread_local! {
+    static COUNTERS: RefCell<Counters> = const { RefCell::new(vec![]) };
+}
+
+/// Adds `amount` to a named counter, creating it if needed. Counters are
+/// reported next to the answer of the part being run.
+pub fn add(name: &'static str, amount: u64) {
+    COUNTERS.with_borrow_mut(|counters| {
+        match counters.iter_mut().find(|(counter, _)| *counter == name) {
+            Some((_, value)) => *value += amount,
+            None => counters.push((name, amount)),
+        }
+    });
+}
+
+pub fn increment(name: &'static str) {
+    add(name, 1);
+}
+
+/// Returns the counters recorded so far, resetting them.
+pub fn take() -> Counters {
+    COUNTERS.with_borrow_mut(std::mem::take)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        take();
+        increment("memo hits");
+        add("nodes popped", 10);
+        increment("memo hits");
+        assert_eq!(take(), vec![("memo hits", 2), ("nodes popped", 10)]);
+        assert_eq!(take(), vec![]);
+    }
+}
@@ -0,0 +1,256 @@
+use std::fmt;
+
+// The letters Advent of Code draws 6 and 10 rows tall, in the order they
+// appear in each font's sheet
+const SMALL_LETTERS: &str = "ABCEFGHIJKLOPRSUYZ";
+const SMALL_FONT: &str = "
+.##..###...##..####.####..##..#..#.###...##.#..#.#.....##..###..###...###.#..#.#...#.####
+#..#.#..#.#..#.#....#....#..#.#..#..#.....#.#.#..#....#..#.#..#.#..#.#....#..#.#...#....#
+#..#.###..#....###..###..#....####..#.....#.##...#....#..#.#..#.#..#.#....#..#..#.#....#.
+####.#..#.#....#....#....#.##.#..#..#.....#.#.#..#....#..#.###..###...##..#..#...#....#..
+#..#.#..#.#..#.#....#....#..#.#..#..#..#..#.#.#..#....#..#.#....#.#.....#.#..#...#...#...
+#..#.###...##..####.#.....###.#..#.###..##..#..#.####..##..#....#..#.###...##....#...####";
+
+const LARGE_LETTERS: &str = "ABCEFGHJKLNPRXZ";
+const LARGE_FONT: &str = "
+..##...#####...####..######.######..####..#....#....###.#....#.#......#....#.#####..#####..#....#.######
+.#..#..#....#.#....#.#......#......#....#.#....#.....#..#...#..#......##...#.#....#.#....#.#....#......#
+#....#.#....#.#......#......#......#......#....#.....#..#..#...#......##...#.#....#.#....#..#..#.......#
+#....#.#....#.#......#......#......#......#....#.....#..#.#....#......#.#..#.#....#.#....#..#..#......#.
+#....#.#####..#......#####..#####..#......######.....#..##.....#......#.#..#.#####..#####....##......#..
+######.#....#.#......#......#......#..###.#....#.....#..##.....#......#..#.#.#......#..#.....##.....#...
+#....#.#....#.#......#......#......#....#.#....#.#...#..#.#....#......#..#.#.#......#...#...#..#...#....
+#....#.#....#.#......#......#......#....#.#....#.#...#..#..#...#......#...##.#......#...#...#..#..#.....
+#....#.#....#.#....#.#......#......#...##.#....#.#...#..#...#..#......#...##.#......#....#.#....#.#.....
+#....#.#####...####..######.#.......###.#.#....#..###...#....#.######.#....#.#......#....#.#....#.######";
+
+/// A glyph as its columns, each column a bitmask of its lit rows.
+type Glyph = Vec<u16>;
+
+#[derive(Debug, PartialEq)]
+pub enum OcrError {
+    /// Only grids as tall as one of the fonts can be read.
+    UnsupportedHeight(usize),
+    /// Some glyphs didn't match any letter. `text` holds the letters that
+    /// were read, with a `?` in place of each unrecognized glyph.
+    Unrecognized {
+        text: String,
+        glyphs: Vec<(usize, String)>,
+    },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedHeight(height) => write!(
+                f,
+                "can't read letters from a grid {height} rows tall (expected 6 or 10)"
+            ),
+            Self::Unrecognized { text, glyphs } => {
+                write!(f, "unrecognized letters in {text:?}")?;
+                for (column, glyph) in glyphs {
+                    write!(f, "\nglyph at column {column}:\n{glyph}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+/// Reads the letters drawn on a grid of `#` (lit) and `.` or space (unlit)
+/// characters, in either of the fonts Advent of Code uses. Letters are
+/// told apart by the blank columns between them.
+pub fn recognize(grid: &str) -> Result<String, OcrError> {
+    let rows = rows(grid);
+    let height = rows.len();
+
+    let font = match height {
+        6 => font(SMALL_LETTERS, SMALL_FONT),
+        10 => font(LARGE_LETTERS, LARGE_FONT),
+        _ => return Err(OcrError::UnsupportedHeight(height)),
+    };
+
+    let mut text = String::new();
+    let mut unrecognized = vec![];
+    for (column, glyph) in segment(&columns(&rows)) {
+        match font.iter().find(|(_, letter)| *letter == glyph) {
+            Some(&(letter, _)) => text.push(letter),
+            None => {
+                text.push('?');
+                unrecognized.push((column, render(&glyph, height)));
+            }
+        }
+    }
+
+    if unrecognized.is_empty() {
+        Ok(text)
+    } else {
+        Err(OcrError::Unrecognized {
+            text,
+            glyphs: unrecognized,
+        })
+    }
+}
+
+/// Returns the rows of a grid without any blank rows around it.
+fn rows(grid: &str) -> Vec<&str> {
+    let rows: Vec<&str> = grid
+        .lines()
+        .map(str::trim_end)
+        .skip_while(|row| row.is_empty())
+        .collect();
+    let height = rows
+        .iter()
+        .rposition(|row| !row.is_empty())
+        .map_or(0, |i| i + 1);
+    rows[..height].to_vec()
+}
+
+fn font(letters: &str, sheet: &str) -> Vec<(char, Glyph)> {
+    let glyphs = segment(&columns(&rows(sheet)));
+    debug_assert_eq!(glyphs.len(), letters.len());
+    letters
+        .chars()
+        .zip(glyphs.into_iter().map(|(_, glyph)| glyph))
+        .collect()
+}
+
+fn columns(rows: &[&str]) -> Vec<u16> {
+    let rows: Vec<Vec<char>> = rows.iter().map(|row| row.chars().collect()).collect();
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    (0..width)
+        .map(|j| {
+            rows.iter().enumerate().fold(0, |bits, (i, row)| {
+                if row.get(j) == Some(&'#') {
+                    bits | (1 << i)
+                } else {
+                    bits
+                }
+            })
+        })
+        .collect()
+}
+
+/// Splits columns into glyphs at blank columns, returning each glyph with
+/// the index of its first column.
+fn segment(columns: &[u16]) -> Vec<(usize, Glyph)> {
+    let mut glyphs = vec![];
+    let mut start = None;
+    for (index, &column) in columns.iter().chain([&0]).enumerate() {
+        match (start, column) {
+            (None, 0) => (),
+            (None, _) => start = Some(index),
+            (Some(first), 0) => {
+                glyphs.push((first, columns[first..index].to_vec()));
+                start = None;
+            }
+            (Some(_), _) => (),
+        }
+    }
+    glyphs
+}
+
+fn render(glyph: &[u16], height: usize) -> String {
+    (0..height)
+        .map(|i| {
+            glyph
+                .iter()
+                .map(|column| if column & (1 << i) != 0 { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Solution;
+
+    #[test]
+    fn it_works() {
+        assert_eq!(recognize(SMALL_FONT), Ok(SMALL_LETTERS.to_string()));
+        assert_eq!(recognize(LARGE_FONT), Ok(LARGE_LETTERS.to_string()));
+
+        let grid = "
+#..#.###..####.
+#..#..#...#....
+####..#...###..
+#..#..#...#....
+#..#..#...#....
+#..#.###..####.";
+        assert_eq!(recognize(grid), Ok("HIE".to_string()));
+
+        let grid = "
+#....#..#....#
+##...#..#....#
+##...#...#..#.
+#.#..#...#..#.
+#.#..#....##..
+#..#.#....##..
+#..#.#...#..#.
+#...##...#..#.
+#...##..#....#
+#....#..#....#";
+        assert_eq!(recognize(grid), Ok("NX".to_string()));
+
+        let grid = "
+.##..#.
+#..#.#.
+#..#.#.
+####.##
+#..#.#.
+#..#.#.";
+        assert_eq!(
+            recognize(grid),
+            Err(OcrError::Unrecognized {
+                text: "A?".to_string(),
+                glyphs: vec![(5, "#.\n#.\n#.\n##\n#.\n#.".to_string())],
+            })
+        );
+        assert_eq!(recognize("#\n#"), Err(OcrError::UnsupportedHeight(2)));
+    }
+
+    /// A solution whose answer is the letters drawn by its input.
+    #[derive(Default)]
+    struct Letters {}
+
+    impl Solution for Letters {
+        type Result = Result<String, OcrError>;
+
+        fn part_1(&self, input: &str) -> Self::Result {
+            recognize(input)
+        }
+
+        fn part_2(&self, input: &str) -> Self::Result {
+            recognize(input)
+        }
+    }
+
+    #[test]
+    fn answers() {
+        let grid = "
+#..#.###..####.
+#..#..#...#....
+####..#...###..
+#..#..#...#....
+#..#..#...#....
+#..#.###..####.";
+        let result = Letters::new().run_part(1, grid).unwrap();
+        assert_eq!(result.answer, "HIE");
+
+        let error = Letters::new().run_part(2, "#\n#").unwrap_err();
+        assert_eq!(error, OcrError::UnsupportedHeight(2).to_string());
+        let grid = "
+.##..#.
+#..#.#.
+#..#.#.
+####.##
+#..#.#.
+#..#.#.";
+        let error = Letters::new().run_part(2, grid).unwrap_err();
+        assert!(error.starts_with("unrecognized letters in \"A?\""));
+    }
+}
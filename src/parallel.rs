@@ -0,0 +1,132 @@
+use std::num::NonZeroUsize;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+
+use crate::{metrics, trace};
+
+// Zero means one thread per available core
+static THREADS: AtomicUsize = AtomicUsize::new(0);
+
+// Some solutions recurse deeply, so threads get as much stack as the main
+// thread usually has rather than the default for spawned threads
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Sets how many threads solutions split their work over, with zero meaning
+/// one per available core.
+pub fn set_threads(threads: usize) {
+    THREADS.store(threads, Ordering::Relaxed);
+}
+
+pub fn threads() -> usize {
+    match THREADS.load(Ordering::Relaxed) {
+        0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        threads => threads,
+    }
+}
+
+/// Calls `f` on every item, spread over scoped threads, and returns the
+/// results in the same order as the items, however the work was split.
+/// Threads take the next item as soon as they finish one, so a few slow items
+/// don't hold up the rest.
+///
+/// Traces from the threads are attributed to the calling thread's day, and
+/// counters they record are added to the calling thread's. If `f` panics, the
+/// remaining items are skipped and the panic is resumed on the calling
+/// thread.
+pub fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let threads = threads().min(items.len());
+    if threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let day = trace::day();
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let work = || {
+        trace::set_day(day);
+        let mut results = vec![];
+        while !stop.load(Ordering::Relaxed) {
+            let index = next.fetch_add(1, Ordering::Relaxed);
+            let Some(item) = items.get(index) else {
+                break;
+            };
+            match panic::catch_unwind(AssertUnwindSafe(|| f(item))) {
+                Ok(result) => results.push((index, result)),
+                Err(payload) => {
+                    stop.store(true, Ordering::Relaxed);
+                    return Err(payload);
+                }
+            }
+        }
+        Ok((results, metrics::take()))
+    };
+
+    let outcomes: Vec<_> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                thread::Builder::new()
+                    .stack_size(STACK_SIZE)
+                    .spawn_scoped(scope, work)
+                    .expect("failed to spawn a thread")
+            })
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().unwrap_or_else(Err))
+            .collect()
+    });
+
+    let mut results = Vec::with_capacity(items.len());
+    for outcome in outcomes {
+        match outcome {
+            Ok((worker_results, counters)) => {
+                results.extend(worker_results);
+                for (name, amount) in counters {
+                    metrics::add(name, amount);
+                }
+            }
+            Err(payload) => panic::resume_unwind(payload),
+        }
+    }
+    results.sort_unstable_by_key(|&(index, _)| index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Puts the global thread count back when a test is done with it, even
+    /// if the test fails.
+    struct RestoreThreads(usize);
+
+    impl Drop for RestoreThreads {
+        fn drop(&mut self) {
+            set_threads(self.0);
+        }
+    }
+
+    #[test]
+    fn it_works() {
+        let _restore = RestoreThreads(THREADS.load(Ordering::Relaxed));
+        set_threads(4);
+        metrics::take();
+        let items: Vec<u64> = (0..1000).collect();
+        let squares = map(&items, |&n| {
+            metrics::increment("items");
+            n * n
+        });
+        assert_eq!(squares, items.iter().map(|n| n * n).collect::<Vec<_>>());
+        assert_eq!(metrics::take(), vec![("items", 1000)]);
+        assert!(map(&[] as &[u64], |&n| n).is_empty());
+
+        let result = panic::catch_unwind(|| map(&items, |&n| assert!(n != 500, "bad item")));
+        assert!(result.is_err());
+    }
+}
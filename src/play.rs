@@ -0,0 +1,247 @@
+use std::io::{self, Write};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+use crate::terminal::{self, Key, RawMode};
+
+/// The most frames a simulation should record, so that long simulations are
+/// sampled rather than kept in full.
+pub const MAX_FRAMES: usize = 500;
+
+const DEFAULT_FPS: u32 = 10;
+const MAX_FPS: u32 = 320;
+
+/// One step of a simulation, drawn as a block of text under a caption.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub caption: String,
+    pub body: String,
+}
+
+impl Frame {
+    pub fn new(caption: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            caption: caption.into(),
+            body: body.into(),
+        }
+    }
+}
+
+/// The frames of a simulation, along with the colors to draw the characters
+/// of their bodies in.
+#[derive(Debug, Default)]
+pub struct Animation {
+    pub frames: Vec<Frame>,
+    pub colors: Vec<(char, &'static str)>,
+}
+
+impl Animation {
+    pub fn new(colors: &[(char, &'static str)]) -> Self {
+        Self {
+            frames: vec![],
+            colors: colors.to_vec(),
+        }
+    }
+
+    pub fn push(&mut self, caption: impl Into<String>, body: impl Into<String>) {
+        self.frames.push(Frame::new(caption, body));
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.frames.len() >= MAX_FRAMES
+    }
+}
+
+struct Player {
+    frame: usize,
+    fps: u32,
+    paused: bool,
+}
+
+impl Player {
+    /// Returns `false` when playback should stop.
+    fn handle_key(&mut self, key: Key, frame_count: usize) -> bool {
+        let last_frame = frame_count.saturating_sub(1);
+        match key {
+            Key::Char(' ') => {
+                // Playing from the end starts over
+                if self.paused && self.frame == last_frame {
+                    self.frame = 0;
+                }
+                self.paused = !self.paused;
+            }
+            Key::Right | Key::Char('l' | 'n') => {
+                self.paused = true;
+                self.frame = (self.frame + 1).min(last_frame);
+            }
+            Key::Left | Key::Char('h' | 'p') => {
+                self.paused = true;
+                self.frame = self.frame.saturating_sub(1);
+            }
+            Key::Up | Key::Char('+' | '=') => self.fps = (self.fps * 2).min(MAX_FPS),
+            Key::Down | Key::Char('-') => self.fps = (self.fps / 2).max(1),
+            Key::Char('g') => self.frame = 0,
+            Key::Char('G') => self.frame = last_frame,
+            Key::Escape | Key::Char('q') => return false,
+            _ => (),
+        }
+        true
+    }
+
+    fn tick(&mut self, frame_count: usize) {
+        if self.paused {
+            return;
+        }
+        if self.frame + 1 < frame_count {
+            self.frame += 1;
+        } else {
+            self.paused = true;
+        }
+    }
+}
+
+/// Plays an animation in the terminal. Space pauses, the arrow keys step
+/// through frames and change the speed, and q quits.
+pub fn play(title: &str, animation: &Animation) -> io::Result<()> {
+    if animation.frames.is_empty() {
+        println!("Nothing to play for {title}");
+        return Ok(());
+    }
+
+    let _raw_mode = RawMode::enable()?;
+    let (sender, receiver) = mpsc::channel();
+    terminal::spawn_key_reader(sender, |key| key);
+
+    let mut stdout = io::stdout();
+    write!(stdout, "{}", terminal::ENTER_SCREEN)?;
+
+    let mut player = Player {
+        frame: 0,
+        fps: DEFAULT_FPS,
+        paused: false,
+    };
+    let frame_count = animation.frames.len();
+
+    let result = loop {
+        if let Err(error) = draw(&mut stdout, title, animation, &player) {
+            break Err(error);
+        }
+        let frame_duration = Duration::from_secs(1) / player.fps;
+        match receiver.recv_timeout(frame_duration) {
+            Ok(key) => {
+                if !player.handle_key(key, frame_count) {
+                    break Ok(());
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => player.tick(frame_count),
+            Err(RecvTimeoutError::Disconnected) => break Ok(()),
+        }
+    };
+
+    write!(stdout, "{}", terminal::LEAVE_SCREEN)?;
+    stdout.flush()?;
+
+    result
+}
+
+fn draw(
+    out: &mut impl Write,
+    title: &str,
+    animation: &Animation,
+    player: &Player,
+) -> io::Result<()> {
+    let frame = &animation.frames[player.frame];
+    let mut screen = String::new();
+    screen.push_str(terminal::HOME);
+
+    let state = if player.paused { "paused" } else { "playing" };
+    let header = format!(
+        "{}{title}{}  frame {}/{}  {} fps  {state}",
+        terminal::BOLD,
+        terminal::RESET,
+        player.frame + 1,
+        animation.frames.len(),
+        player.fps,
+    );
+    terminal::push_line(&mut screen, &header);
+    terminal::push_line(&mut screen, &frame.caption);
+    terminal::push_line(&mut screen, "");
+    for line in frame.body.lines() {
+        terminal::push_line(&mut screen, &colorize(line, &animation.colors));
+    }
+    terminal::push_line(&mut screen, "");
+    terminal::push_line(
+        &mut screen,
+        &format!(
+            "{}space: play/pause   left/right: step   up/down: speed   g/G: first/last   q: quit{}",
+            terminal::DIM,
+            terminal::RESET
+        ),
+    );
+    screen.push_str(terminal::CLEAR_BELOW);
+
+    out.write_all(screen.as_bytes())?;
+    out.flush()
+}
+
+/// Colors each character of a line by the given colors, only switching
+/// colors where they change.
+fn colorize(line: &str, colors: &[(char, &'static str)]) -> String {
+    if colors.is_empty() {
+        return line.to_string();
+    }
+
+    let mut colored = String::new();
+    let mut current = "";
+    for ch in line.chars() {
+        let color = colors
+            .iter()
+            .find(|(c, _)| *c == ch)
+            .map_or("", |&(_, color)| color);
+        if color != current {
+            colored.push_str(terminal::RESET);
+            colored.push_str(color);
+            current = color;
+        }
+        colored.push(ch);
+    }
+    colored.push_str(terminal::RESET);
+    colored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let mut player = Player {
+            frame: 0,
+            fps: DEFAULT_FPS,
+            paused: false,
+        };
+        player.tick(3);
+        player.tick(3);
+        player.tick(3);
+        assert_eq!((player.frame, player.paused), (2, true));
+
+        player.handle_key(Key::Left, 3);
+        player.handle_key(Key::Up, 3);
+        assert_eq!((player.frame, player.fps), (1, DEFAULT_FPS * 2));
+        player.handle_key(Key::Char('G'), 3);
+        player.handle_key(Key::Char(' '), 3);
+        assert_eq!((player.frame, player.paused), (0, false));
+        assert!(!player.handle_key(Key::Char('q'), 3));
+
+        let colors = [('#', terminal::RED)];
+        assert_eq!(
+            colorize("..##", &colors),
+            format!(
+                "..{}{}##{}",
+                terminal::RESET,
+                terminal::RED,
+                terminal::RESET
+            )
+        );
+    }
+}
@@ -0,0 +1,325 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::client::Client;
+use crate::html::{self, Token};
+use crate::input;
+use crate::terminal::{BOLD, CYAN, RESET};
+
+/// The width puzzle text is wrapped to.
+const WIDTH: usize = 80;
+
+/// Where a day's puzzle page is cached, next to the inputs since puzzle text
+/// shouldn't be shared either.
+pub fn path(day: u8) -> PathBuf {
+    input::dir().join(format!("puzzles/day{day}.html"))
+}
+
+/// Returns a day's puzzle page, downloading it unless the cached page already
+/// has both parts or there is no client to download it with. The cached page
+/// is used when downloading fails, so puzzles can be read offline.
+pub fn load(client: Option<&Client>, day: u8, refresh: bool) -> io::Result<String> {
+    let path = path(day);
+    let cached = fs::read_to_string(&path).ok();
+    if let Some(page) = &cached {
+        if !refresh && articles(page).len() >= 2 {
+            return Ok(page.clone());
+        }
+    }
+
+    let error = match client.map(|client| client.fetch_puzzle(day)) {
+        Some(Ok(page)) => {
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(&path, &page)?;
+            return Ok(page);
+        }
+        Some(Err(error)) => error,
+        None => io::Error::other(
+            "set AOC_SESSION or `paths.session` in aoc.toml to your Advent of Code session \
+             cookie to download it",
+        ),
+    };
+    match cached {
+        Some(page) => {
+            eprintln!("Showing the cached page, since it can't be downloaded: {error}");
+            Ok(page)
+        }
+        None => Err(error),
+    }
+}
+
+/// Returns the tokens inside each `<article>` in a page, which hold the
+/// puzzle's description with one article per part.
+pub fn articles(page: &str) -> Vec<Vec<Token>> {
+    let mut articles = vec![];
+    let mut current: Option<Vec<Token>> = None;
+    let mut depth = 0;
+
+    for token in html::tokenize(page) {
+        match &token {
+            Token::StartTag { name, .. } if name == "article" => {
+                depth += 1;
+                if depth == 1 {
+                    current = Some(vec![]);
+                    continue;
+                }
+            }
+            Token::EndTag { name } if name == "article" && depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    articles.extend(current.take());
+                    continue;
+                }
+            }
+            _ => (),
+        }
+        if let Some(article) = &mut current {
+            article.push(token);
+        }
+    }
+
+    articles
+}
+
+/// Renders an article as text for the terminal, wrapped to 80 columns. When
+/// `styled`, emphasis and code are shown with terminal colors; otherwise
+/// they're marked with `*` and backticks.
+pub fn render(article: &[Token], styled: bool) -> String {
+    let mut renderer = Renderer {
+        styled,
+        ..Renderer::default()
+    };
+    for token in article {
+        match token {
+            Token::StartTag {
+                name, self_closing, ..
+            } => {
+                if name == "br" {
+                    renderer.end_line();
+                } else if !self_closing {
+                    renderer.start(name);
+                }
+            }
+            Token::EndTag { name } => renderer.end(name),
+            Token::Text(text) => renderer.text(text),
+        }
+    }
+    renderer.end_block();
+    renderer.out.trim_end().to_string() + "\n"
+}
+
+#[derive(Default)]
+struct Renderer {
+    out: String,
+    styled: bool,
+    /// How many characters are shown on the current line
+    column: usize,
+    /// Where lines start, inside lists and code blocks
+    indent: usize,
+    /// Whether there was whitespace before the word being built
+    space: bool,
+    /// The word being built, which can span several elements, and how many
+    /// characters it shows. It's only written out once it's complete, so that
+    /// it can be wrapped as a whole.
+    word: String,
+    word_len: usize,
+    /// The elements currently open, with the style each one started
+    open: Vec<(String, Option<&'static str>)>,
+    lists: usize,
+    pre: usize,
+}
+
+impl Renderer {
+    fn start(&mut self, name: &str) {
+        match name {
+            "p" | "h1" | "h2" | "h3" => self.end_block(),
+            "ul" | "ol" => {
+                if self.lists == 0 {
+                    self.end_block();
+                } else {
+                    self.end_line();
+                }
+                self.lists += 1;
+            }
+            "li" => {
+                self.end_line();
+                let bullet = 2 * self.lists;
+                self.out.push_str(&" ".repeat(bullet - 2));
+                self.out.push_str("- ");
+                self.column = bullet;
+                self.indent = bullet;
+            }
+            "pre" => {
+                self.end_block();
+                self.pre += 1;
+                self.indent += 4;
+            }
+            _ => (),
+        }
+
+        // Without colors, markers inside code would look like part of it
+        let in_code = self.pre > 0 || self.is_open("code");
+        let style = match name {
+            "h1" | "h2" | "h3" if self.styled => Some(BOLD),
+            "em" if self.styled || !in_code => Some(BOLD),
+            "code" if !in_code => Some(CYAN),
+            _ => None,
+        };
+        if let Some(style) = style {
+            if self.styled {
+                self.word.push_str(style);
+            } else {
+                self.push(marker(style));
+            }
+        }
+        self.open.push((name.to_string(), style));
+    }
+
+    fn end(&mut self, name: &str) {
+        let Some(index) = self.open.iter().rposition(|(open, _)| open == name) else {
+            return;
+        };
+        let closed = self.open.split_off(index);
+        if self.styled {
+            // Styles can't be ended one at a time, so the ones still open are
+            // started again
+            if closed.iter().any(|(_, style)| style.is_some()) {
+                self.word.push_str(RESET);
+                for (_, style) in &self.open {
+                    self.word.push_str(style.unwrap_or_default());
+                }
+            }
+        } else {
+            for style in closed.iter().rev().filter_map(|(_, style)| *style) {
+                self.push(marker(style));
+            }
+        }
+
+        match name {
+            "p" | "h1" | "h2" | "h3" => self.end_block(),
+            "li" => self.end_line(),
+            "ul" | "ol" => {
+                self.lists -= 1;
+                self.indent = 2 * self.lists;
+                if self.lists == 0 {
+                    self.end_block();
+                }
+            }
+            "pre" => {
+                self.pre -= 1;
+                self.indent -= 4;
+                self.end_block();
+            }
+            _ => (),
+        }
+    }
+
+    fn is_open(&self, name: &str) -> bool {
+        self.open.iter().any(|(open, _)| open == name)
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.pre > 0 {
+            for (index, line) in text.split('\n').enumerate() {
+                if index > 0 && self.column == 0 && self.word_len == 0 {
+                    self.out.push('\n');
+                } else if index > 0 {
+                    self.end_line();
+                }
+                self.push(line);
+            }
+            return;
+        }
+
+        let is_breaking_space = |ch: char| ch.is_whitespace() && ch != '\u{a0}';
+        for (index, word) in text.split(is_breaking_space).enumerate() {
+            if index > 0 {
+                self.write_word();
+                self.space = true;
+            }
+            self.push(word);
+        }
+    }
+
+    /// Adds to the word being built.
+    fn push(&mut self, s: &str) {
+        self.word.push_str(s);
+        self.word_len += s.chars().count();
+    }
+
+    /// Writes out the word being built, wrapping the line first if the word
+    /// doesn't fit. In code blocks, lines are never wrapped.
+    fn write_word(&mut self) {
+        if self.word_len > 0 {
+            let space = usize::from(self.space && self.column > self.indent);
+            if self.pre == 0
+                && self.column > self.indent
+                && self.column + space + self.word_len > WIDTH
+            {
+                self.out.push('\n');
+                self.column = 0;
+            } else if space > 0 {
+                self.out.push(' ');
+                self.column += 1;
+            }
+            if self.column == 0 {
+                self.out.push_str(&" ".repeat(self.indent));
+                self.column = self.indent;
+            }
+            self.column += self.word_len;
+            self.space = false;
+        }
+        self.out.push_str(&self.word);
+        self.word.clear();
+        self.word_len = 0;
+    }
+
+    fn end_line(&mut self) {
+        self.write_word();
+        if self.column > 0 {
+            self.out.push('\n');
+            self.column = 0;
+        }
+        self.space = false;
+    }
+
+    /// Ends the current line, leaving a blank line before whatever comes next.
+    fn end_block(&mut self) {
+        self.end_line();
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+}
+
+fn marker(style: &str) -> &'static str {
+    match style {
+        CYAN => "`",
+        _ => "*",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let page = include_str!("fixtures/day1.html");
+        let parts = articles(page);
+        assert_eq!(parts.len(), 2);
+        assert_eq!(
+            render(&parts[0], false),
+            include_str!("fixtures/day1-part1.txt")
+        );
+        assert_eq!(
+            render(&parts[1], false),
+            include_str!("fixtures/day1-part2.txt")
+        );
+        assert!(render(&parts[0], true).contains("\x1b[36m\x1b[1m142\x1b[0m\x1b[36m\x1b[0m"));
+
+        let page = include_str!("fixtures/day2.html");
+        assert_eq!(articles(page).len(), 1);
+    }
+}
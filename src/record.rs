@@ -0,0 +1,304 @@
+use std::cell::{Cell, RefCell};
+
+use crate::json::Value;
+
+/// The most snapshots kept for one run. Past this, every other snapshot is
+/// dropped and only half as many are sampled from then on, so that long runs
+/// are still covered from start to finish.
+pub const MAX_SNAPSHOTS: usize = 1_000;
+
+/// A grid as a solution saw it at one point, with some cells highlighted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub caption: String,
+    pub grid: String,
+    pub highlights: Vec<(usize, usize)>,
+}
+
+impl Snapshot {
+    pub fn new(caption: impl Into<String>, grid: impl Into<String>) -> Self {
+        Self {
+            caption: caption.into(),
+            grid: grid.into(),
+            highlights: vec![],
+        }
+    }
+
+    pub fn highlight(mut self, cells: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        self.highlights.extend(cells);
+        self
+    }
+}
+
+#[derive(Default)]
+struct Recording {
+    // Each snapshot, and whether it has to be kept when thinning them out
+    snapshots: Vec<(Snapshot, bool)>,
+    stride: usize,
+    offered: usize,
+}
+
+thread_local! {
+    static RECORDING: Cell<bool> = const { Cell::new(false) };
+    static SNAPSHOTS: RefCell<Recording> = RefCell::default();
+}
+
+/// Starts recording snapshots taken on this thread.
+pub fn start() {
+    SNAPSHOTS.set(Recording {
+        stride: 1,
+        ..Recording::default()
+    });
+    RECORDING.set(true);
+}
+
+/// Stops recording, returning the snapshots taken.
+pub fn finish() -> Vec<Snapshot> {
+    RECORDING.set(false);
+    let recording = SNAPSHOTS.take();
+    recording
+        .snapshots
+        .into_iter()
+        .map(|(snapshot, _)| snapshot)
+        .collect()
+}
+
+/// Whether snapshots are being recorded, for solutions that need to do extra
+/// work to take them.
+#[inline]
+pub fn is_recording() -> bool {
+    RECORDING.get()
+}
+
+/// Offers a snapshot of an intermediate state, which may be skipped to keep
+/// the recording small. The snapshot is only built when it will be kept, so
+/// this costs next to nothing when not recording.
+#[inline]
+pub fn sample(snapshot: impl FnOnce() -> Snapshot) {
+    if is_recording() {
+        push(snapshot, false);
+    }
+}
+
+/// Records a snapshot that is always kept, such as a final state.
+#[inline]
+pub fn snapshot(snapshot: impl FnOnce() -> Snapshot) {
+    if is_recording() {
+        push(snapshot, true);
+    }
+}
+
+fn push(snapshot: impl FnOnce() -> Snapshot, keep: bool) {
+    SNAPSHOTS.with_borrow_mut(|recording| {
+        recording.offered += 1;
+        if !keep && recording.offered % recording.stride != 0 {
+            return;
+        }
+        recording.snapshots.push((snapshot(), keep));
+
+        if recording.snapshots.len() > MAX_SNAPSHOTS {
+            let mut index = 0;
+            recording.snapshots.retain(|&(_, keep)| {
+                index += 1;
+                keep || index % 2 == 0
+            });
+            recording.stride *= 2;
+        }
+    });
+}
+
+/// Builds a single HTML page for scrubbing through snapshots in a browser,
+/// with everything it needs inlined so that it works offline.
+pub fn to_html(title: &str, snapshots: &[Snapshot]) -> String {
+    let mut previous_grid = None;
+    let snapshots = snapshots.iter().map(|snapshot| {
+        // Grids that don't change between snapshots are only stored once
+        let grid = if previous_grid == Some(&snapshot.grid) {
+            Value::Null
+        } else {
+            previous_grid = Some(&snapshot.grid);
+            Value::Array(snapshot.grid.lines().map(Value::from).collect())
+        };
+        let highlights = snapshot
+            .highlights
+            .iter()
+            .map(|&(i, j)| Value::Array(vec![i.into(), j.into()]));
+        Value::object([
+            ("caption", snapshot.caption.as_str().into()),
+            ("grid", grid),
+            ("highlights", Value::Array(highlights.collect())),
+        ])
+    });
+    let data = Value::object([
+        ("title", title.into()),
+        ("snapshots", Value::Array(snapshots.collect())),
+    ]);
+
+    // Keeps the data from closing the script element it's embedded in
+    let data = data.to_string().replace("</", "<\\/");
+    HTML_TEMPLATE
+        .replace("{{title}}", &escape_html(title))
+        .replace("{{data}}", &data)
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const HTML_TEMPLATE: &str = r##"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{{title}}</title>
+<style>
+  body { background: #0f0f23; color: #cccccc; font-family: monospace; margin: 2em; }
+  button { font-family: monospace; }
+  #controls { display: flex; gap: 1em; align-items: center; margin: 1em 0; }
+  #scrubber { flex: 1; }
+  canvas { image-rendering: pixelated; border: 1px solid #333340; }
+</style>
+</head>
+<body>
+<h1>{{title}}</h1>
+<div id="controls">
+  <button id="play">play</button>
+  <input id="scrubber" type="range" min="0" value="0">
+  <span id="position"></span>
+  <select id="speed">
+    <option value="1">1 fps</option>
+    <option value="10" selected>10 fps</option>
+    <option value="30">30 fps</option>
+    <option value="60">60 fps</option>
+  </select>
+</div>
+<div id="caption"></div>
+<canvas id="canvas"></canvas>
+<p>space: play/pause, left/right: step</p>
+<script>
+const data = {{data}};
+
+// Grids left out because they didn't change are filled in from the last one
+let grid = [];
+for (const snapshot of data.snapshots) {
+  grid = snapshot.grid || grid;
+  snapshot.grid = grid;
+}
+
+const canvas = document.getElementById("canvas");
+const context = canvas.getContext("2d");
+const scrubber = document.getElementById("scrubber");
+const playButton = document.getElementById("play");
+const speed = document.getElementById("speed");
+scrubber.max = Math.max(data.snapshots.length - 1, 0);
+
+function color(ch) {
+  if (ch === "." || ch === " ") return "#0f0f23";
+  if (ch === "#") return "#3c3c5a";
+  if (ch === "O") return "#ffff66";
+  if (ch >= "0" && ch <= "9") {
+    const level = 30 + (ch.charCodeAt(0) - 48) * 18;
+    return `rgb(${level}, ${level}, ${level + 30})`;
+  }
+  return "#cccccc";
+}
+
+function draw() {
+  const index = Number(scrubber.value);
+  const snapshot = data.snapshots[index];
+  document.getElementById("position").textContent = `${index + 1}/${data.snapshots.length}`;
+  if (!snapshot) return;
+  document.getElementById("caption").textContent = snapshot.caption;
+
+  const rows = snapshot.grid;
+  const width = Math.max(1, ...rows.map((row) => row.length));
+  const cell = Math.max(1, Math.floor(800 / Math.max(width, rows.length)));
+  canvas.width = width * cell;
+  canvas.height = rows.length * cell;
+  rows.forEach((row, i) => {
+    [...row].forEach((ch, j) => {
+      context.fillStyle = color(ch);
+      context.fillRect(j * cell, i * cell, cell, cell);
+    });
+  });
+  context.fillStyle = "rgba(0, 204, 0, 0.85)";
+  for (const [i, j] of snapshot.highlights) {
+    context.fillRect(j * cell, i * cell, cell, cell);
+  }
+}
+
+let timer = null;
+function setPlaying(playing) {
+  clearInterval(timer);
+  timer = null;
+  playButton.textContent = playing ? "pause" : "play";
+  if (!playing) return;
+  if (Number(scrubber.value) >= Number(scrubber.max)) scrubber.value = 0;
+  timer = setInterval(() => {
+    if (Number(scrubber.value) >= Number(scrubber.max)) return setPlaying(false);
+    scrubber.value = Number(scrubber.value) + 1;
+    draw();
+  }, 1000 / Number(speed.value));
+}
+
+function step(delta) {
+  setPlaying(false);
+  scrubber.value = Number(scrubber.value) + delta;
+  draw();
+}
+
+scrubber.addEventListener("input", draw);
+playButton.addEventListener("click", () => setPlaying(timer === null));
+speed.addEventListener("change", () => { if (timer !== null) setPlaying(true); });
+document.addEventListener("keydown", (event) => {
+  if (event.key === " ") { event.preventDefault(); setPlaying(timer === null); }
+  if (event.key === "ArrowLeft") step(-1);
+  if (event.key === "ArrowRight") step(1);
+});
+draw();
+</script>
+</body>
+</html>
+"##;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        sample(|| unreachable!("snapshots aren't built when not recording"));
+
+        start();
+        for n in 0..(MAX_SNAPSHOTS * 2) {
+            sample(|| Snapshot::new(n.to_string(), ""));
+        }
+        snapshot(|| Snapshot::new("last", ""));
+        let snapshots = finish();
+        assert!(!is_recording());
+        assert!(snapshots.len() <= MAX_SNAPSHOTS);
+        assert_eq!(snapshots.last().unwrap().caption, "last");
+        // What's left of the run is still evenly spaced
+        let numbers: Vec<usize> = snapshots[..snapshots.len() - 1]
+            .iter()
+            .map(|snapshot| snapshot.caption.parse().unwrap())
+            .collect();
+        assert!(numbers
+            .windows(2)
+            .all(|w| w[1] - w[0] == numbers[1] - numbers[0]));
+        assert!(*numbers.last().unwrap() >= MAX_SNAPSHOTS * 2 - 4);
+
+        let snapshots = [
+            Snapshot::new("</script>", "#.\n.#").highlight([(0, 1)]),
+            Snapshot::new("same grid", "#.\n.#"),
+        ];
+        let html = to_html("Day <14>", &snapshots);
+        assert!(html.contains("<title>Day &lt;14&gt;</title>"));
+        assert!(html.contains(concat!(
+            r##"{"caption":"<\/script>","grid":["#.",".#"],"highlights":[[0,1]]},"##,
+            r##"{"caption":"same grid","grid":null,"highlights":[]}"##,
+        )));
+    }
+}
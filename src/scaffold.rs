@@ -0,0 +1,165 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const DAYS_START: &str = "\ndays! {\n";
+const ALL_DAYS_START: &str = "\nall-days = [";
+
+/// Starts a new day from a template, adding it to the days in `lib.rs` and
+/// giving it a feature in `Cargo.toml`. Days are added in order, so the new
+/// day has to come right after the last one. Returns the path of the new
+/// day's source file.
+pub fn new_day(day: u8) -> io::Result<PathBuf> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let path = root.join(format!("src/day{day}.rs"));
+    if path.exists() {
+        return Err(io::Error::other(format!(
+            "{} already exists",
+            path.display()
+        )));
+    }
+
+    let lib_path = root.join("src/lib.rs");
+    let lib = fs::read_to_string(&lib_path)?;
+    let lib = add_day(&lib, day).map_err(io::Error::other)?;
+    let manifest_path = root.join("Cargo.toml");
+    let manifest = fs::read_to_string(&manifest_path)?;
+    let manifest = add_feature(&manifest, day).map_err(io::Error::other)?;
+
+    fs::write(&path, template(day))?;
+    fs::write(lib_path, lib)?;
+    fs::write(manifest_path, manifest)?;
+    Ok(path)
+}
+
+/// Adds `day` after the last of the days in the source of `lib.rs`.
+fn add_day(lib: &str, day: u8) -> Result<String, String> {
+    let not_found = || "couldn't find the days in lib.rs".to_string();
+    let start = lib.find(DAYS_START).ok_or_else(not_found)? + DAYS_START.len();
+    let end = start + lib[start..].find("\n}").ok_or_else(not_found)? + 1;
+    let last_day: u8 = lib[start..end]
+        .lines()
+        .last()
+        .and_then(|line| line.trim().split_once(" => "))
+        .and_then(|(last_day, _)| last_day.parse().ok())
+        .ok_or_else(not_found)?;
+
+    if day != last_day + 1 {
+        return Err(format!(
+            "days are added in order, and the next day is {}",
+            last_day + 1
+        ));
+    }
+    Ok(format!(
+        "{}    {day} => day{day}::Day{day} if feature = \"day{day}\",\n{}",
+        &lib[..end],
+        &lib[end..]
+    ))
+}
+
+/// Adds a feature for `day` to the source of `Cargo.toml`, after the previous
+/// day's, and turns it on with `all-days`.
+fn add_feature(manifest: &str, day: u8) -> Result<String, String> {
+    let not_found = || {
+        format!(
+            "couldn't find the feature for day {} in Cargo.toml",
+            day - 1
+        )
+    };
+
+    let start = manifest.find(ALL_DAYS_START).ok_or_else(not_found)?;
+    let end = start + manifest[start..].find(']').ok_or_else(not_found)?;
+    let previous = format!("\"day{}\"", day - 1);
+    let position = start
+        + manifest[start..end]
+            .rfind(&previous)
+            .ok_or_else(not_found)?;
+    let position = position + previous.len();
+    let manifest = format!(
+        "{}, \"day{day}\"{}",
+        &manifest[..position],
+        &manifest[position..]
+    );
+
+    let previous = format!("\nday{} = []\n", day - 1);
+    let position = manifest.find(&previous).ok_or_else(not_found)? + previous.len();
+    Ok(format!(
+        "{}day{day} = []\n{}",
+        &manifest[..position],
+        &manifest[position..]
+    ))
+}
+
+fn template(day: u8) -> String {
+    format!(
+        r#"use crate::Solution;
+
+#[derive(Default)]
+pub struct Day{day} {{}}
+
+impl Solution for Day{day} {{
+    type Result = usize;
+
+    fn part_1(&self, _input: &str) -> Self::Result {{
+        0
+    }}
+
+    fn part_2(&self, _input: &str) -> Self::Result {{
+        0
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    const INPUT: &str = "";
+
+    #[test]
+    fn part_1() {{
+        assert_eq!(Day{day}::new().part_1(INPUT), 0);
+    }}
+
+    #[test]
+    fn part_2() {{
+        assert_eq!(Day{day}::new().part_2(INPUT), 0);
+    }}
+}}
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let lib = "use x;\n\ndays! {\n    20 => day20::Day20 if feature = \"day20\",\n}\n";
+        assert_eq!(
+            add_day(lib, 21),
+            Ok(
+                "use x;\n\ndays! {\n    20 => day20::Day20 if feature = \"day20\",\n    \
+                21 => day21::Day21 if feature = \"day21\",\n}\n"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            add_day(lib, 23),
+            Err("days are added in order, and the next day is 21".to_string())
+        );
+
+        let manifest =
+            "[features]\nall-days = [\n    \"day19\", \"day20\",\n]\nday19 = []\nday20 = []\n\n";
+        assert_eq!(
+            add_feature(manifest, 21),
+            Ok(
+                "[features]\nall-days = [\n    \"day19\", \"day20\", \"day21\",\n]\n\
+                day19 = []\nday20 = []\nday21 = []\n\n"
+                    .to_string()
+            )
+        );
+        assert!(add_feature(manifest, 23).is_err());
+        assert!(template(21).contains("pub struct Day21 {}"));
+    }
+}
@@ -0,0 +1,190 @@
+use std::io::{self, Read};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::Sender;
+use std::thread;
+
+pub const CLEAR_LINE: &str = "\x1b[K";
+pub const CLEAR_BELOW: &str = "\x1b[J";
+pub const HOME: &str = "\x1b[H";
+pub const ENTER_SCREEN: &str = "\x1b[?1049h\x1b[?25l";
+pub const LEAVE_SCREEN: &str = "\x1b[?25h\x1b[?1049l";
+
+pub const RESET: &str = "\x1b[0m";
+pub const BOLD: &str = "\x1b[1m";
+pub const DIM: &str = "\x1b[2m";
+pub const RED: &str = "\x1b[31m";
+pub const GREEN: &str = "\x1b[32m";
+pub const YELLOW: &str = "\x1b[33m";
+pub const BLUE: &str = "\x1b[34m";
+pub const CYAN: &str = "\x1b[36m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Enter,
+    Escape,
+    Char(char),
+}
+
+/// Puts the terminal into unbuffered, no-echo mode for as long as the guard
+/// is alive. This goes through `stty` so that no terminal bindings are needed.
+pub struct RawMode {
+    saved_settings: String,
+}
+
+impl RawMode {
+    pub fn enable() -> io::Result<Self> {
+        let output = Command::new("stty")
+            .arg("-g")
+            .stdin(Stdio::inherit())
+            .output()?;
+        if !output.status.success() {
+            return Err(io::Error::other("stdin is not a terminal"));
+        }
+        let saved_settings = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+        stty(&["-icanon", "-echo", "-isig", "min", "1"])?;
+
+        Ok(Self { saved_settings })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = stty(&[&self.saved_settings]);
+    }
+}
+
+fn stty(args: &[&str]) -> io::Result<()> {
+    Command::new("stty").args(args).status().map(|_| ())
+}
+
+/// Adds a line to a screen being drawn, clearing whatever was drawn after it
+/// on that line before.
+pub fn push_line(screen: &mut String, line: &str) {
+    screen.push_str(line);
+    screen.push_str(CLEAR_LINE);
+    screen.push('\n');
+}
+
+/// Reads key presses from stdin on a background thread, converting each one
+/// with `to_message` before sending it.
+pub fn spawn_key_reader<T, F>(sender: Sender<T>, to_message: F)
+where
+    T: Send + 'static,
+    F: Fn(Key) -> T + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut stdin = io::stdin();
+        let mut buffer = [0; 16];
+        while let Ok(length) = stdin.read(&mut buffer) {
+            if length == 0 {
+                break;
+            }
+            for key in parse_keys(&buffer[..length]) {
+                if sender.send(to_message(key)).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+}
+
+fn parse_keys(bytes: &[u8]) -> Vec<Key> {
+    let mut keys = vec![];
+    let mut index = 0;
+    while index < bytes.len() {
+        let key = match &bytes[index..] {
+            [0x1b, b'[', b'A', ..] => Key::Up,
+            [0x1b, b'[', b'B', ..] => Key::Down,
+            [0x1b, b'[', b'C', ..] => Key::Right,
+            [0x1b, b'[', b'D', ..] => Key::Left,
+            [0x1b, ..] => Key::Escape,
+            [b'\r' | b'\n', ..] => Key::Enter,
+            // Ctrl-C arrives as a byte since signals are disabled
+            [0x03, ..] => Key::Escape,
+            [byte, ..] => Key::Char(*byte as char),
+            [] => unreachable!(),
+        };
+        index += match key {
+            Key::Up | Key::Down | Key::Left | Key::Right => 3,
+            _ => 1,
+        };
+        keys.push(key);
+    }
+    keys
+}
+
+/// Lines up rows of cells in columns, with a rule under the first row.
+pub fn format_table(rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|column| {
+            rows.iter()
+                .filter_map(|row| row.get(column))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut table = String::new();
+    for (index, row) in rows.iter().enumerate() {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{cell:<width$}"))
+            .collect();
+        table.push_str(cells.join("  ").trim_end());
+        table.push('\n');
+
+        if index == 0 {
+            let rule: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+            table.push_str(&rule.join("  "));
+            table.push('\n');
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_keys() {
+        assert_eq!(
+            parse_keys(b"\x1b[Aq\r\x1b\x1b[D"),
+            vec![Key::Up, Key::Char('q'), Key::Enter, Key::Escape, Key::Left]
+        );
+    }
+
+    #[test]
+    fn formats_table() {
+        let rows = vec![
+            vec![
+                "Input".to_string(),
+                "Part 1".to_string(),
+                "Status".to_string(),
+            ],
+            vec!["alice".to_string(), "4361".to_string(), "ok".to_string()],
+            vec![
+                "bob".to_string(),
+                "12 (expected 13)".to_string(),
+                "MISMATCH".to_string(),
+            ],
+        ];
+        assert_eq!(
+            format_table(&rows),
+            "\
+Input  Part 1            Status
+-----  ----------------  --------
+alice  4361              ok
+bob    12 (expected 13)  MISMATCH
+"
+        );
+    }
+}
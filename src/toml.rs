@@ -0,0 +1,229 @@
+use std::fmt;
+
+/// A value in a TOML document. Only the parts of TOML that the config file
+/// needs are supported: strings, integers, floats, booleans and tables.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::String(s) => write!(f, "{s:?}"),
+            Self::Integer(n) => write!(f, "{n}"),
+            Self::Float(n) => write!(f, "{n}"),
+            Self::Boolean(b) => write!(f, "{b}"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses a TOML document into its keys and values, in order. Keys in a
+/// table are prefixed with the table's name, as in `bench.runs`.
+pub fn parse(s: &str) -> Result<Vec<(String, Value)>, ParseError> {
+    let mut entries: Vec<(String, Value)> = vec![];
+    let mut table = String::new();
+
+    for (index, line) in s.lines().enumerate() {
+        let error = |message: String| ParseError {
+            line: index + 1,
+            message,
+        };
+
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix('[') {
+            let (name, rest) = rest
+                .split_once(']')
+                .ok_or_else(|| error("expected `]` after the table name".to_string()))?;
+            if !is_comment(rest) {
+                return Err(error(format!("unexpected {rest:?} after the table name")));
+            }
+            table = parse_key(name).map_err(error)?;
+            continue;
+        }
+
+        let (key, rest) = line
+            .split_once('=')
+            .ok_or_else(|| error("expected `key = value`".to_string()))?;
+        let key = parse_key(key).map_err(error)?;
+        let key = if table.is_empty() {
+            key
+        } else {
+            format!("{table}.{key}")
+        };
+        let (value, rest) = parse_value(rest.trim()).map_err(error)?;
+        if !is_comment(rest) {
+            return Err(error(format!("unexpected {rest:?} after the value")));
+        }
+
+        if entries.iter().any(|(existing, _)| *existing == key) {
+            return Err(error(format!("`{key}` is defined more than once")));
+        }
+        entries.push((key, value));
+    }
+
+    Ok(entries)
+}
+
+fn is_comment(s: &str) -> bool {
+    let s = s.trim();
+    s.is_empty() || s.starts_with('#')
+}
+
+/// Parses a bare or dotted key, such as `runs` or `bench.runs`.
+fn parse_key(s: &str) -> Result<String, String> {
+    let parts: Vec<&str> = s.split('.').map(str::trim).collect();
+    let is_bare = |part: &&str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-')
+    };
+    if parts.iter().all(is_bare) {
+        Ok(parts.join("."))
+    } else {
+        Err(format!("{:?} isn't a valid key", s.trim()))
+    }
+}
+
+/// Parses the value at the start of `s`, returning it and the rest of `s`.
+fn parse_value(s: &str) -> Result<(Value, &str), String> {
+    if let Some(rest) = s.strip_prefix('"') {
+        return parse_basic_string(rest);
+    }
+    if let Some(rest) = s.strip_prefix('\'') {
+        let (string, rest) = rest
+            .split_once('\'')
+            .ok_or("expected `'` to close the string")?;
+        return Ok((Value::String(string.to_string()), rest));
+    }
+
+    let end = s.find([' ', '\t', '#']).unwrap_or(s.len());
+    let (token, rest) = s.split_at(end);
+    let value = match token {
+        "true" => Value::Boolean(true),
+        "false" => Value::Boolean(false),
+        "" => return Err("expected a value".to_string()),
+        _ => {
+            let number = token.replace('_', "");
+            if let Ok(n) = number.parse() {
+                Value::Integer(n)
+            } else if let Ok(n) = number.parse() {
+                Value::Float(n)
+            } else {
+                return Err(format!("{token:?} isn't a string, number or boolean"));
+            }
+        }
+    };
+    Ok((value, rest))
+}
+
+fn parse_basic_string(s: &str) -> Result<(Value, &str), String> {
+    let mut string = String::new();
+    let mut chars = s.char_indices();
+    while let Some((index, ch)) = chars.next() {
+        match ch {
+            '"' => return Ok((Value::String(string), &s[index + 1..])),
+            '\\' => {
+                let escaped = match chars.next().map(|(_, ch)| ch) {
+                    Some('"') => '"',
+                    Some('\\') => '\\',
+                    Some('n') => '\n',
+                    Some('t') => '\t',
+                    Some('r') => '\r',
+                    other => {
+                        return Err(format!("unsupported escape `\\{}`", other.unwrap_or(' ')))
+                    }
+                };
+                string.push(escaped);
+            }
+            ch => string.push(ch),
+        }
+    }
+    Err("expected `\"` to close the string".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let document = r#"
+# Settings
+year = 2023
+format = "json" # trailing comment
+
+[paths]
+inputs = 'C:\inputs'
+session = "~/.aoc \"session\""
+
+[bench]
+runs = 1_000
+timeout = 2.5
+warmup = false
+"#;
+        assert_eq!(
+            parse(document),
+            Ok(vec![
+                ("year".to_string(), Value::Integer(2023)),
+                ("format".to_string(), Value::String("json".to_string())),
+                (
+                    "paths.inputs".to_string(),
+                    Value::String("C:\\inputs".to_string())
+                ),
+                (
+                    "paths.session".to_string(),
+                    Value::String("~/.aoc \"session\"".to_string())
+                ),
+                ("bench.runs".to_string(), Value::Integer(1000)),
+                ("bench.timeout".to_string(), Value::Float(2.5)),
+                ("bench.warmup".to_string(), Value::Boolean(false)),
+            ])
+        );
+
+        let error = |line, message: &str| {
+            Err(ParseError {
+                line,
+                message: message.to_string(),
+            })
+        };
+        assert_eq!(
+            parse("a = 1\na = 2"),
+            error(2, "`a` is defined more than once")
+        );
+        assert_eq!(
+            parse("a = \"b"),
+            error(1, "expected `\"` to close the string")
+        );
+        assert_eq!(
+            parse("a = 1 2"),
+            error(1, "unexpected \" 2\" after the value")
+        );
+        assert_eq!(
+            parse("[a\nb = 1"),
+            error(1, "expected `]` after the table name")
+        );
+    }
+}
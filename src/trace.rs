@@ -0,0 +1,218 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+/// The environment variable holding the trace filter, e.g. `debug` or
+/// `info,20=trace,day17=debug`.
+pub const FILTER_VAR: &str = "AOC_LOG";
+
+/// The environment variable naming a file to write traces to instead of
+/// stderr.
+pub const FILE_VAR: &str = "AOC_LOG_FILE";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    fn from_str(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "error" => Some(Self::Error),
+            "warn" => Some(Self::Warn),
+            "info" => Some(Self::Info),
+            "debug" => Some(Self::Debug),
+            "trace" => Some(Self::Trace),
+            _ => None,
+        }
+    }
+
+    fn from_verbosity(verbosity: u8) -> Option<Self> {
+        match verbosity {
+            0 => None,
+            1 => Some(Self::Info),
+            2 => Some(Self::Debug),
+            _ => Some(Self::Trace),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::Error => "ERROR",
+            Self::Warn => "WARN",
+            Self::Info => "INFO",
+            Self::Debug => "DEBUG",
+            Self::Trace => "TRACE",
+        };
+        f.pad(label)
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Filter {
+    default: Option<Level>,
+    days: HashMap<u8, Level>,
+}
+
+impl Filter {
+    fn parse(spec: &str) -> Self {
+        let mut filter = Self::default();
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((day, level)) => {
+                    let day = day.trim_start_matches("day").parse();
+                    if let (Ok(day), Some(level)) = (day, Level::from_str(level)) {
+                        filter.days.insert(day, level);
+                    }
+                }
+                None => filter.default = Level::from_str(directive).or(filter.default),
+            }
+        }
+        filter
+    }
+
+    fn max_level(&self) -> u8 {
+        let day_levels = self.days.values().copied();
+        self.default
+            .into_iter()
+            .chain(day_levels)
+            .max()
+            .map_or(0, |level| level as u8)
+    }
+
+    fn level(&self, day: u8) -> Option<Level> {
+        self.days.get(&day).copied().or(self.default)
+    }
+}
+
+// Checked before anything else, so that disabled traces cost a single load
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+static FILTER: OnceLock<Filter> = OnceLock::new();
+static OUTPUT: OnceLock<Mutex<Box<dyn Write + Send>>> = OnceLock::new();
+
+thread_local! {
+    static CURRENT_DAY: Cell<u8> = const { Cell::new(0) };
+}
+
+/// Sets up tracing from the `AOC_LOG` filter, raising its default level by
+/// the number of `-v` flags given. Traces go to `log_file`, then the file
+/// named by `AOC_LOG_FILE`, and otherwise to stderr.
+pub fn init(verbosity: u8, log_file: Option<&Path>) -> io::Result<()> {
+    let mut filter = Filter::parse(&std::env::var(FILTER_VAR).unwrap_or_default());
+    filter.default = filter.default.max(Level::from_verbosity(verbosity));
+
+    let log_file = log_file
+        .map(Path::to_path_buf)
+        .or_else(|| std::env::var_os(FILE_VAR).map(Into::into));
+    let output: Box<dyn Write + Send> = match log_file {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(io::stderr()),
+    };
+
+    let max_level = filter.max_level();
+    let _ = OUTPUT.set(Mutex::new(output));
+    let _ = FILTER.set(filter);
+    MAX_LEVEL.store(max_level, Ordering::Relaxed);
+
+    Ok(())
+}
+
+/// Sets the day that traces on this thread are attributed to.
+pub fn set_day(day: u8) {
+    CURRENT_DAY.with(|current_day| current_day.set(day));
+}
+
+/// Returns the day that traces on this thread are attributed to.
+pub fn day() -> u8 {
+    CURRENT_DAY.with(Cell::get)
+}
+
+#[inline]
+pub fn enabled(level: Level) -> bool {
+    if level as u8 > MAX_LEVEL.load(Ordering::Relaxed) {
+        return false;
+    }
+    let day = day();
+    FILTER
+        .get()
+        .and_then(|filter| filter.level(day))
+        .is_some_and(|max_level| level <= max_level)
+}
+
+pub fn write(level: Level, args: fmt::Arguments<'_>) {
+    let Some(output) = OUTPUT.get() else {
+        return;
+    };
+    let day = day();
+    if let Ok(mut output) = output.lock() {
+        let _ = writeln!(output, "[day{day} {level:<5}] {args}");
+    }
+}
+
+#[allow(unused_macros)]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::trace::enabled($level) {
+            $crate::trace::write($level, format_args!($($arg)+));
+        }
+    };
+}
+
+#[allow(unused_macros)]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::trace::log!($crate::trace::Level::Error, $($arg)+) };
+}
+
+#[allow(unused_macros)]
+macro_rules! warning {
+    ($($arg:tt)+) => { $crate::trace::log!($crate::trace::Level::Warn, $($arg)+) };
+}
+
+#[allow(unused_macros)]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::trace::log!($crate::trace::Level::Info, $($arg)+) };
+}
+
+#[allow(unused_macros)]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::trace::log!($crate::trace::Level::Debug, $($arg)+) };
+}
+
+#[allow(unused_macros)]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::trace::log!($crate::trace::Level::Trace, $($arg)+) };
+}
+
+#[allow(unused_imports)]
+pub(crate) use {debug, error, info, log, trace, warning};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_filters() {
+        let filter = Filter::parse("info, 20=trace,day17=debug,nonsense");
+        assert_eq!(filter.default, Some(Level::Info));
+        assert_eq!(filter.level(20), Some(Level::Trace));
+        assert_eq!(filter.level(17), Some(Level::Debug));
+        assert_eq!(filter.level(3), Some(Level::Info));
+        assert_eq!(filter.max_level(), Level::Trace as u8);
+
+        let filter = Filter::parse("8=warn");
+        assert_eq!(filter.level(8), Some(Level::Warn));
+        assert_eq!(filter.level(9), None);
+        assert_eq!(Filter::parse("").max_level(), 0);
+    }
+}
@@ -0,0 +1,489 @@
+use std::io::{self, Write};
+use std::panic;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::answers::Answers;
+use crate::terminal::{self, Key, RawMode};
+use crate::{input, try_run_part, PartResult, DAYS};
+
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+const FRAME_DURATION: Duration = Duration::from_millis(80);
+const COLUMNS: usize = 5;
+const CELL_WIDTH: usize = 22;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PartStatus {
+    Pending,
+    Queued,
+    Running,
+    Solved,
+    Unverified,
+    Wrong,
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+struct PartState {
+    status: PartStatus,
+    duration: Option<Duration>,
+    // The answer, or the panic message if the part failed
+    output: Option<String>,
+    expected: Option<String>,
+}
+
+impl PartState {
+    fn new(status: PartStatus) -> Self {
+        Self {
+            status,
+            duration: None,
+            output: None,
+            expected: None,
+        }
+    }
+}
+
+struct DayState {
+    has_solution: bool,
+    has_input: bool,
+    parts: [PartState; 2],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DayStatus {
+    Unsolved,
+    NoInput,
+    Ready,
+    Running,
+    Solved,
+    Unverified,
+    Wrong,
+}
+
+impl DayState {
+    fn status(&self) -> DayStatus {
+        use PartStatus::*;
+
+        let statuses = self.parts.iter().map(|part| part.status);
+        if !self.has_solution {
+            DayStatus::Unsolved
+        } else if !self.has_input {
+            DayStatus::NoInput
+        } else if statuses.clone().any(|s| s == Running || s == Queued) {
+            DayStatus::Running
+        } else if statuses.clone().any(|s| s == Wrong || s == Failed) {
+            DayStatus::Wrong
+        } else if statuses.clone().all(|s| s == Solved) {
+            DayStatus::Solved
+        } else if statuses.clone().any(|s| s == Pending) {
+            DayStatus::Ready
+        } else {
+            DayStatus::Unverified
+        }
+    }
+}
+
+enum Message {
+    Key(Key),
+    NoInput {
+        day: u8,
+    },
+    Started {
+        day: u8,
+        part: u8,
+    },
+    Finished {
+        day: u8,
+        part: u8,
+        outcome: Result<PartResult, String>,
+        expected: Option<String>,
+    },
+}
+
+struct Dashboard {
+    days: Vec<DayState>,
+    selected: usize,
+    started_at: Instant,
+}
+
+/// Shows a full-screen calendar of every day, running solutions on a
+/// background thread so their progress can be followed live.
+pub fn run() -> io::Result<()> {
+    let _raw_mode = RawMode::enable()?;
+
+    // Panics are shown in the dashboard instead of being printed over it.
+    // The hook goes in before any thread starts, so that none of them can
+    // panic through the default hook while the terminal is in raw mode.
+    let panic_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let (sender, receiver) = mpsc::channel();
+    terminal::spawn_key_reader(sender.clone(), Message::Key);
+    let jobs = spawn_worker(sender);
+
+    let mut dashboard = Dashboard::new();
+    dashboard.queue_all(&jobs);
+
+    let mut stdout = io::stdout();
+    write!(stdout, "{}", terminal::ENTER_SCREEN)?;
+
+    let result = loop {
+        if let Err(error) = dashboard.draw(&mut stdout) {
+            break Err(error);
+        }
+        match receiver.recv_timeout(FRAME_DURATION) {
+            Ok(Message::Key(key)) => {
+                if !dashboard.handle_key(key, &jobs) {
+                    break Ok(());
+                }
+            }
+            Ok(message) => dashboard.update(message),
+            Err(RecvTimeoutError::Timeout) => (),
+            Err(RecvTimeoutError::Disconnected) => break Ok(()),
+        }
+    };
+
+    panic::set_hook(panic_hook);
+    write!(stdout, "{}", terminal::LEAVE_SCREEN)?;
+    stdout.flush()?;
+
+    result
+}
+
+/// Runs queued days one at a time, so that the timings of one day aren't
+/// skewed by another running alongside it.
+fn spawn_worker(sender: Sender<Message>) -> Sender<u8> {
+    let (jobs, queue) = mpsc::channel::<u8>();
+
+    thread::spawn(move || {
+        for day in queue {
+            let Ok(input) = input::read(day) else {
+                if sender.send(Message::NoInput { day }).is_err() {
+                    return;
+                }
+                continue;
+            };
+            let answers = Answers::load();
+
+            for part in [1, 2] {
+                if sender.send(Message::Started { day, part }).is_err() {
+                    return;
+                }
+
+                let outcome = try_run_part(day, part, &input);
+
+                let expected = answers.get(day, part).map(str::to_string);
+                let message = Message::Finished {
+                    day,
+                    part,
+                    outcome,
+                    expected,
+                };
+                if sender.send(message).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+
+    jobs
+}
+
+impl Dashboard {
+    fn new() -> Self {
+        let days = (1..=25)
+            .map(|day| DayState {
+                has_solution: DAYS.contains(&day),
+                has_input: input::exists(day),
+                parts: [
+                    PartState::new(PartStatus::Pending),
+                    PartState::new(PartStatus::Pending),
+                ],
+            })
+            .collect();
+
+        Self {
+            days,
+            selected: 0,
+            started_at: Instant::now(),
+        }
+    }
+
+    fn queue(&mut self, day: u8, jobs: &Sender<u8>) {
+        let state = &mut self.days[day as usize - 1];
+        if !state.has_solution {
+            return;
+        }
+        state.has_input = true;
+        state.parts = [
+            PartState::new(PartStatus::Queued),
+            PartState::new(PartStatus::Queued),
+        ];
+        let _ = jobs.send(day);
+    }
+
+    fn queue_all(&mut self, jobs: &Sender<u8>) {
+        for day in 1..=25 {
+            if self.days[day as usize - 1].has_input {
+                self.queue(day, jobs);
+            }
+        }
+    }
+
+    /// Returns `false` when the dashboard should be closed.
+    fn handle_key(&mut self, key: Key, jobs: &Sender<u8>) -> bool {
+        match key {
+            Key::Left | Key::Char('h') => self.selected = self.selected.saturating_sub(1),
+            Key::Right | Key::Char('l') => self.selected = (self.selected + 1).min(24),
+            Key::Up | Key::Char('k') if self.selected >= COLUMNS => self.selected -= COLUMNS,
+            Key::Down | Key::Char('j') if self.selected + COLUMNS < 25 => self.selected += COLUMNS,
+            Key::Enter | Key::Char('r') => self.queue(self.selected as u8 + 1, jobs),
+            Key::Char('a') => self.queue_all(jobs),
+            Key::Escape | Key::Char('q') => return false,
+            _ => (),
+        }
+        true
+    }
+
+    fn update(&mut self, message: Message) {
+        match message {
+            Message::Key(_) => (),
+            Message::NoInput { day } => {
+                let state = &mut self.days[day as usize - 1];
+                state.has_input = false;
+                state.parts = [
+                    PartState::new(PartStatus::Pending),
+                    PartState::new(PartStatus::Pending),
+                ];
+            }
+            Message::Started { day, part } => {
+                let state = &mut self.days[day as usize - 1].parts[part as usize - 1];
+                state.status = PartStatus::Running;
+            }
+            Message::Finished {
+                day,
+                part,
+                outcome,
+                expected,
+            } => {
+                let state = &mut self.days[day as usize - 1].parts[part as usize - 1];
+                match outcome {
+                    Ok(result) => {
+                        state.status = match &expected {
+                            Some(expected) if *expected == result.answer => PartStatus::Solved,
+                            Some(_) => PartStatus::Wrong,
+                            None => PartStatus::Unverified,
+                        };
+                        state.duration = Some(result.duration);
+                        state.output = Some(result.answer);
+                    }
+                    Err(message) => {
+                        state.status = PartStatus::Failed;
+                        state.output = Some(message);
+                    }
+                }
+                state.expected = expected;
+            }
+        }
+    }
+
+    fn draw(&self, out: &mut impl Write) -> io::Result<()> {
+        let spinner = SPINNER[(self.started_at.elapsed().as_millis() / FRAME_DURATION.as_millis())
+            as usize
+            % SPINNER.len()];
+
+        let mut screen = String::new();
+        screen.push_str(terminal::HOME);
+        terminal::push_line(
+            &mut screen,
+            &format!("{}  Advent of Code 2023{}", terminal::BOLD, terminal::RESET),
+        );
+        terminal::push_line(&mut screen, "");
+
+        for (row_index, days) in self.days.chunks(COLUMNS).enumerate() {
+            let cells: Vec<Vec<String>> = days
+                .iter()
+                .enumerate()
+                .map(|(column_index, state)| {
+                    let index = row_index * COLUMNS + column_index;
+                    render_cell(index as u8 + 1, state, index == self.selected, spinner)
+                })
+                .collect();
+            for line in 0..cells[0].len() {
+                let line: String = cells.iter().map(|cell| cell[line].as_str()).collect();
+                terminal::push_line(&mut screen, &line);
+            }
+        }
+
+        terminal::push_line(&mut screen, "");
+        let selected_day = self.selected + 1;
+        for (index, part) in self.days[self.selected].parts.iter().enumerate() {
+            let details = match (&part.output, &part.expected) {
+                (Some(output), Some(expected)) if part.status == PartStatus::Wrong => {
+                    format!("{output} (expected {expected})")
+                }
+                (Some(output), _) => output.clone(),
+                (None, _) => "-".to_string(),
+            };
+            terminal::push_line(
+                &mut screen,
+                &format!("  Day {selected_day} part {}: {details}", index + 1),
+            );
+        }
+        terminal::push_line(&mut screen, "");
+        terminal::push_line(
+            &mut screen,
+            &format!(
+                "{}  arrows/hjkl: select   enter/r: run day   a: run all   q: quit{}",
+                terminal::DIM,
+                terminal::RESET
+            ),
+        );
+        screen.push_str(terminal::CLEAR_BELOW);
+
+        out.write_all(screen.as_bytes())?;
+        out.flush()
+    }
+}
+
+fn render_cell(day: u8, state: &DayState, selected: bool, spinner: char) -> Vec<String> {
+    let inner_width = CELL_WIDTH - 2;
+    let status = state.status();
+    let (label, color) = match status {
+        DayStatus::Unsolved => ("unsolved", terminal::DIM),
+        DayStatus::NoInput => ("no input", terminal::DIM),
+        DayStatus::Ready => ("ready", ""),
+        DayStatus::Running => ("running", terminal::YELLOW),
+        DayStatus::Solved => ("solved", terminal::GREEN),
+        DayStatus::Unverified => ("unverified", terminal::BLUE),
+        DayStatus::Wrong => ("wrong", terminal::RED),
+    };
+
+    let header = format!(" Day {day:<2}{label:>width$} ", width = inner_width - 8);
+    let part_lines = state.parts.iter().enumerate().map(|(index, part)| {
+        let symbol = match part.status {
+            PartStatus::Pending => '·',
+            PartStatus::Queued => '…',
+            PartStatus::Running => spinner,
+            PartStatus::Solved => '✔',
+            PartStatus::Unverified => '?',
+            PartStatus::Wrong => '✘',
+            PartStatus::Failed => '!',
+        };
+        let duration = part
+            .duration
+            .map(|duration| format!("{duration:.2?}"))
+            .unwrap_or_default();
+        let line = format!(
+            " {} {symbol} {duration:>w$} ",
+            index + 1,
+            w = inner_width - 6
+        );
+        if status == DayStatus::Unsolved || status == DayStatus::NoInput {
+            " ".repeat(inner_width)
+        } else {
+            line
+        }
+    });
+
+    let border = if selected {
+        terminal::CYAN
+    } else {
+        terminal::DIM
+    };
+    let reset = terminal::RESET;
+    let horizontal = "─".repeat(inner_width);
+
+    let mut lines = vec![format!("{border}┌{horizontal}┐{reset}")];
+    lines.push(format!(
+        "{border}│{reset}{color}{header}{reset}{border}│{reset}"
+    ));
+    for line in part_lines {
+        lines.push(format!("{border}│{reset}{line}{border}│{reset}"));
+    }
+    lines.push(format!("{border}└{horizontal}┘{reset}"));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(parts: [PartStatus; 2]) -> DayState {
+        DayState {
+            has_solution: true,
+            has_input: true,
+            parts: parts.map(PartState::new),
+        }
+    }
+
+    #[test]
+    fn day_status() {
+        use PartStatus::*;
+
+        let mut unsolved = day([Solved, Solved]);
+        unsolved.has_solution = false;
+        assert_eq!(unsolved.status(), DayStatus::Unsolved);
+        let mut no_input = day([Pending, Pending]);
+        no_input.has_input = false;
+        assert_eq!(no_input.status(), DayStatus::NoInput);
+
+        assert_eq!(day([Solved, Queued]).status(), DayStatus::Running);
+        assert_eq!(day([Failed, Solved]).status(), DayStatus::Wrong);
+        assert_eq!(day([Solved, Solved]).status(), DayStatus::Solved);
+        assert_eq!(day([Solved, Pending]).status(), DayStatus::Ready);
+        assert_eq!(day([Solved, Unverified]).status(), DayStatus::Unverified);
+    }
+
+    #[test]
+    fn keys() {
+        let (jobs, queue) = mpsc::channel();
+        let mut dashboard = Dashboard {
+            days: (0..25).map(|_| day([PartStatus::Pending; 2])).collect(),
+            selected: 0,
+            started_at: Instant::now(),
+        };
+
+        for key in [Key::Right, Key::Char('j'), Key::Down, Key::Up, Key::Left] {
+            assert!(dashboard.handle_key(key, &jobs));
+        }
+        assert_eq!(dashboard.selected, COLUMNS);
+        assert!(dashboard.handle_key(Key::Char('k'), &jobs));
+        assert!(dashboard.handle_key(Key::Char('k'), &jobs));
+        assert_eq!(dashboard.selected, 0);
+
+        assert!(dashboard.handle_key(Key::Enter, &jobs));
+        assert_eq!(queue.try_recv(), Ok(1));
+        assert_eq!(dashboard.days[0].status(), DayStatus::Running);
+
+        assert!(!dashboard.handle_key(Key::Char('q'), &jobs));
+    }
+
+    #[test]
+    fn cells() {
+        let mut state = day([PartStatus::Solved, PartStatus::Running]);
+        state.parts[0].duration = Some(Duration::from_millis(5));
+        let lines = render_cell(7, &state, false, '⠋');
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].contains(" Day 7 ") && lines[1].contains("running"));
+        assert!(lines[2].contains("1 ✔") && lines[2].contains("5.00ms"));
+        assert!(lines[3].contains("2 ⠋"));
+
+        // Every cell is the same width once the colours are left out
+        let width = |line: &str| {
+            let mut in_escape = false;
+            line.chars()
+                .filter(|&ch| {
+                    in_escape |= ch == '\x1b';
+                    let visible = !in_escape;
+                    in_escape &= ch != 'm';
+                    visible
+                })
+                .count()
+        };
+        assert!(lines.iter().all(|line| width(line) == CELL_WIDTH));
+        let selected = render_cell(7, &state, true, '⠋');
+        assert!(selected[0].starts_with(terminal::CYAN));
+    }
+}
@@ -0,0 +1,187 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::crypto::{self, Key, Nonce, KEY_LEN, NONCE_LEN};
+use crate::input;
+
+/// The start of every encrypted input, which also marks its format.
+const MAGIC: &[u8] = b"aoc-input-v1\n";
+
+static DIR: OnceLock<PathBuf> = OnceLock::new();
+static KEY_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// The directory encrypted inputs are kept in, one `day<N>.enc` file per day.
+/// Unlike plain inputs, these can be committed. This is `inputs` in the
+/// project root unless another directory was set.
+pub fn dir() -> PathBuf {
+    DIR.get()
+        .cloned()
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs"))
+}
+
+/// Keeps encrypted inputs in another directory for the rest of the run. Only
+/// the first directory set takes effect.
+pub fn set_dir(dir: PathBuf) {
+    let _ = DIR.set(dir);
+}
+
+pub fn path(day: u8) -> PathBuf {
+    dir().join(format!("day{day}.enc"))
+}
+
+/// The file holding the key inputs are encrypted with, as hex. This is
+/// outside the project so that it never gets committed with the inputs.
+pub fn key_path() -> PathBuf {
+    KEY_PATH.get().cloned().unwrap_or_else(|| {
+        let home = std::env::var_os("HOME").unwrap_or_default();
+        Path::new(&home).join(".config/aoc/key")
+    })
+}
+
+/// Reads the key from another file for the rest of the run. Only the first
+/// file set takes effect.
+pub fn set_key_path(path: PathBuf) {
+    let _ = KEY_PATH.set(path);
+}
+
+/// Writes a new random key to the key file, which must not exist yet.
+pub fn generate_key() -> io::Result<PathBuf> {
+    let path = key_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let key: Key = random_bytes()?;
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options
+        .open(&path)
+        .map_err(|error| io::Error::new(error.kind(), format!("{}: {error}", path.display())))?;
+    writeln!(file, "{}", to_hex(&key))?;
+    Ok(path)
+}
+
+fn load_key() -> io::Result<Key> {
+    let path = key_path();
+    let contents = fs::read_to_string(&path).map_err(|error| {
+        io::Error::new(
+            error.kind(),
+            format!("can't read the key at {}: {error}", path.display()),
+        )
+    })?;
+    from_hex(contents.trim())
+        .and_then(|key| key.try_into().ok())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "{} doesn't hold a {KEY_LEN} byte key in hex",
+                    path.display()
+                ),
+            )
+        })
+}
+
+/// Encrypts a day's input into the encrypted input directory. Returns
+/// whether the encrypted input changed, since an input that is already
+/// encrypted is left alone to keep it from showing up as changed in git.
+pub fn encrypt(day: u8) -> io::Result<bool> {
+    let key = load_key()?;
+    let input = fs::read(input::path(day))?;
+
+    let path = path(day);
+    if let Ok(existing) = fs::read(&path) {
+        if open(&key, day, &existing).ok().as_deref() == Some(&input[..]) {
+            return Ok(false);
+        }
+    }
+
+    let nonce: Nonce = random_bytes()?;
+    let mut contents = MAGIC.to_vec();
+    contents.extend(nonce);
+    contents.extend(crypto::seal(&key, &nonce, &aad(day), &input));
+
+    fs::create_dir_all(dir())?;
+    fs::write(path, contents)?;
+    Ok(true)
+}
+
+/// Reads and decrypts a day's encrypted input.
+pub fn decrypt(day: u8) -> io::Result<String> {
+    let key = load_key()?;
+    let path = path(day);
+    let contents = fs::read(&path)?;
+    let input = open(&key, day, &contents).map_err(|error| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("can't decrypt {}: {error}", path.display()),
+        )
+    })?;
+    String::from_utf8(input).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+fn open(key: &Key, day: u8, contents: &[u8]) -> Result<Vec<u8>, String> {
+    let rest = contents
+        .strip_prefix(MAGIC)
+        .ok_or("not an encrypted input")?;
+    if rest.len() < NONCE_LEN {
+        return Err("the file is truncated".to_string());
+    }
+    let (nonce, sealed) = rest.split_at(NONCE_LEN);
+    let nonce: Nonce = nonce.try_into().unwrap();
+    crypto::open(key, &nonce, &aad(day), sealed).map_err(|error| error.to_string())
+}
+
+/// Inputs are tied to their day, so that one day's input can't be passed off
+/// as another's by renaming it.
+fn aad(day: u8) -> Vec<u8> {
+    format!("day{day}").into_bytes()
+}
+
+fn random_bytes<const N: usize>() -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    File::open("/dev/urandom")?.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) || !s.is_ascii() {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let key: Key = random_bytes().unwrap();
+        let nonce: Nonce = random_bytes().unwrap();
+        let mut contents = MAGIC.to_vec();
+        contents.extend(nonce);
+        contents.extend(crypto::seal(&key, &nonce, &aad(3), b"467..114..\n"));
+
+        assert_eq!(open(&key, 3, &contents), Ok(b"467..114..\n".to_vec()));
+        assert!(open(&key, 4, &contents).is_err());
+        assert_eq!(
+            open(&key, 3, b"467..114..\n"),
+            Err("not an encrypted input".to_string())
+        );
+
+        assert_eq!(from_hex(&to_hex(&key)), Some(key.to_vec()));
+        assert_eq!(from_hex("0g"), None);
+    }
+}
@@ -0,0 +1,96 @@
+use std::panic;
+
+use crate::answers::Answers;
+use crate::batch::{status_label, summarize, Status};
+use crate::terminal::format_table;
+use crate::{input, json, try_run_part, Format};
+
+/// Runs the given parts of the given days and checks their answers against
+/// the `answers` file. Returns whether every answer that could be checked
+/// was correct.
+pub fn verify(days: &[u8], parts: &[u8], format: Format) -> bool {
+    let answers = Answers::load();
+
+    // Failures are reported in the table instead of as they happen
+    let panic_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut checks = vec![];
+    for &day in days {
+        let input = input::read(day);
+        for &part in parts {
+            let expected = answers.get(day, part);
+            let (answer, status) = match &input {
+                Err(_) => (
+                    format!("no input at {}", input::path(day).display()),
+                    Status::Failed,
+                ),
+                Ok(input) => match try_run_part(day, part, input) {
+                    Ok(result) => {
+                        let status = match expected {
+                            Some(expected) if expected == result.answer => Status::Correct,
+                            Some(_) => Status::Mismatch,
+                            None => Status::Unverified,
+                        };
+                        (result.answer, status)
+                    }
+                    Err(error) => (error, Status::Failed),
+                },
+            };
+            checks.push((day, part, answer, expected.map(str::to_string), status));
+        }
+    }
+
+    panic::set_hook(panic_hook);
+
+    let statuses: Vec<Status> = checks.iter().map(|check| check.4).collect();
+    let passed = matches!(summarize(&statuses), Status::Correct | Status::Unverified);
+
+    match format {
+        Format::Text => {
+            let mut rows = vec![["Day", "Part", "Answer", "Expected", "Status"]
+                .map(str::to_string)
+                .to_vec()];
+            for (day, part, answer, expected, status) in &checks {
+                rows.push(vec![
+                    day.to_string(),
+                    part.to_string(),
+                    answer.clone(),
+                    expected.clone().unwrap_or_else(|| "-".to_string()),
+                    status_label(*status).to_string(),
+                ]);
+            }
+            print!("{}", format_table(&rows));
+
+            let count = |status| statuses.iter().filter(|&&s| s == status).count();
+            println!(
+                "\n{} parts: {} correct, {} unverified, {} mismatched, {} failed",
+                statuses.len(),
+                count(Status::Correct),
+                count(Status::Unverified),
+                count(Status::Mismatch),
+                count(Status::Failed),
+            );
+        }
+        Format::Json => {
+            let checks = checks
+                .into_iter()
+                .map(|(day, part, answer, expected, status)| {
+                    json::Value::object([
+                        ("day", day.into()),
+                        ("part", part.into()),
+                        ("answer", answer.into()),
+                        ("expected", expected.into()),
+                        ("status", status_label(status).to_lowercase().into()),
+                    ])
+                });
+            let output = json::Value::object([
+                ("checks", json::Value::Array(checks.collect())),
+                ("passed", passed.into()),
+            ]);
+            println!("{output}");
+        }
+    }
+
+    passed
+}
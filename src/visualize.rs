@@ -0,0 +1,263 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub type Color = (u8, u8, u8);
+
+pub const BACKGROUND: Color = (15, 15, 35);
+pub const DIM: Color = (60, 60, 90);
+pub const FOREGROUND: Color = (204, 204, 204);
+pub const HIGHLIGHT: Color = (255, 255, 102);
+pub const GREEN: Color = (0, 153, 0);
+pub const RED: Color = (230, 60, 60);
+
+// Images are scaled up so that each cell is at least a few pixels wide,
+// until they are about this many pixels across
+const TARGET_SIZE: usize = 800;
+
+/// Colors for the characters of a grid, with a fallback for any character
+/// that isn't listed.
+pub struct Palette {
+    colors: Vec<(char, Color)>,
+    default: Color,
+}
+
+impl Palette {
+    pub fn new(colors: &[(char, Color)], default: Color) -> Self {
+        Self {
+            colors: colors.to_vec(),
+            default,
+        }
+    }
+
+    pub fn color(&self, ch: char) -> Color {
+        self.colors
+            .iter()
+            .find(|(c, _)| *c == ch)
+            .map_or(self.default, |&(_, color)| color)
+    }
+}
+
+/// A grid of colored cells with outlines drawn over it, which can be saved as
+/// a PPM or SVG image. Cells are addressed by row and column.
+pub struct Picture {
+    width: usize,
+    height: usize,
+    cells: Vec<Color>,
+    // Closed polygons through the centers of cells
+    outlines: Vec<(Vec<(f64, f64)>, Color)>,
+}
+
+impl Picture {
+    pub fn new(width: usize, height: usize, background: Color) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![background; width * height],
+            outlines: vec![],
+        }
+    }
+
+    pub fn from_grid(grid: &str, palette: &Palette) -> Self {
+        let rows: Vec<Vec<char>> = grid.lines().map(|line| line.chars().collect()).collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let mut picture = Self::new(width, rows.len(), palette.default);
+        for (i, row) in rows.iter().enumerate() {
+            for (j, &ch) in row.iter().enumerate() {
+                picture.cells[i * width + j] = palette.color(ch);
+            }
+        }
+        picture
+    }
+
+    /// Colors the given cells, ignoring any outside of the picture.
+    pub fn fill(&mut self, cells: impl IntoIterator<Item = (usize, usize)>, color: Color) {
+        for (i, j) in cells {
+            if i < self.height && j < self.width {
+                self.cells[i * self.width + j] = color;
+            }
+        }
+    }
+
+    /// Draws a closed polygon through the given (row, column) points.
+    pub fn outline(&mut self, points: Vec<(f64, f64)>, color: Color) {
+        self.outlines.push((points, color));
+    }
+
+    fn scale(&self) -> usize {
+        (TARGET_SIZE / self.width.max(self.height).max(1)).clamp(1, 16)
+    }
+
+    pub fn to_ppm(&self, scale: usize) -> Vec<u8> {
+        let (width, height) = (self.width * scale, self.height * scale);
+        let mut pixels = vec![BACKGROUND; width * height];
+        for y in 0..height {
+            for x in 0..width {
+                pixels[y * width + x] = self.cells[(y / scale) * self.width + x / scale];
+            }
+        }
+
+        let to_pixel = |(i, j): (f64, f64)| {
+            let center = scale as f64 / 2.0;
+            (
+                (j * scale as f64 + center) as isize,
+                (i * scale as f64 + center) as isize,
+            )
+        };
+        for (points, color) in &self.outlines {
+            let closing_edge = points.last().zip(points.first());
+            let edges = points.windows(2).map(|edge| (&edge[0], &edge[1]));
+            for (&from, &to) in edges.chain(closing_edge) {
+                for (x, y) in line(to_pixel(from), to_pixel(to)) {
+                    if (0..width as isize).contains(&x) && (0..height as isize).contains(&y) {
+                        pixels[y as usize * width + x as usize] = *color;
+                    }
+                }
+            }
+        }
+
+        let mut ppm = format!("P6\n{width} {height}\n255\n").into_bytes();
+        for (r, g, b) in pixels {
+            ppm.extend([r, g, b]);
+        }
+        ppm
+    }
+
+    pub fn to_svg(&self, scale: usize) -> String {
+        let (width, height) = (self.width, self.height);
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+             viewBox=\"0 0 {width} {height}\" shape-rendering=\"crispEdges\">\n",
+            width * scale,
+            height * scale,
+        );
+
+        // Runs of same-colored cells in a row are drawn as one rectangle
+        for i in 0..height {
+            let row = &self.cells[i * width..(i + 1) * width];
+            let mut j = 0;
+            while j < width {
+                let run = row[j..].iter().take_while(|&&c| c == row[j]).count();
+                let _ = writeln!(
+                    svg,
+                    "<rect x=\"{j}\" y=\"{i}\" width=\"{run}\" height=\"1\" fill=\"{}\"/>",
+                    rgb(row[j])
+                );
+                j += run;
+            }
+        }
+
+        for (points, color) in &self.outlines {
+            let points: Vec<String> = points
+                .iter()
+                .map(|(i, j)| format!("{},{}", j + 0.5, i + 0.5))
+                .collect();
+            let _ = writeln!(
+                svg,
+                "<polygon points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"0.25\" \
+                 shape-rendering=\"geometricPrecision\"/>",
+                points.join(" "),
+                rgb(*color)
+            );
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Writes the picture to `<name>.ppm` and `<name>.svg` in a directory,
+    /// returning the paths written.
+    pub fn save(&self, dir: &Path, name: &str) -> io::Result<[PathBuf; 2]> {
+        fs::create_dir_all(dir)?;
+        let scale = self.scale();
+        let ppm_path = dir.join(format!("{name}.ppm"));
+        let svg_path = dir.join(format!("{name}.svg"));
+        fs::write(&ppm_path, self.to_ppm(scale))?;
+        fs::write(&svg_path, self.to_svg(scale))?;
+        Ok([ppm_path, svg_path])
+    }
+}
+
+/// Scales points with arbitrary coordinates to fit in a picture at most
+/// `max_size` cells across, returning the picture's size and the scaled points.
+pub fn fit(points: &[(isize, isize)], max_size: usize) -> ((usize, usize), Vec<(f64, f64)>) {
+    let min_i = points.iter().map(|p| p.0).min().unwrap_or(0);
+    let max_i = points.iter().map(|p| p.0).max().unwrap_or(0);
+    let min_j = points.iter().map(|p| p.1).min().unwrap_or(0);
+    let max_j = points.iter().map(|p| p.1).max().unwrap_or(0);
+
+    let span = (max_i - min_i).max(max_j - min_j).max(1) as f64;
+    let factor = ((max_size - 1) as f64 / span).min(1.0);
+    let scaled = points
+        .iter()
+        .map(|&(i, j)| {
+            (
+                ((i - min_i) as f64 * factor).round(),
+                ((j - min_j) as f64 * factor).round(),
+            )
+        })
+        .collect();
+
+    let height = ((max_i - min_i) as f64 * factor).round() as usize + 1;
+    let width = ((max_j - min_j) as f64 * factor).round() as usize + 1;
+    ((width, height), scaled)
+}
+
+fn rgb((r, g, b): Color) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+// https://en.wikipedia.org/wiki/Bresenham%27s_line_algorithm
+fn line((x0, y0): (isize, isize), (x1, y1): (isize, isize)) -> Vec<(isize, isize)> {
+    let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+    let (sx, sy) = ((x1 - x0).signum(), (y1 - y0).signum());
+    let (mut x, mut y, mut error) = (x0, y0, dx + dy);
+    let mut points = vec![(x, y)];
+    while (x, y) != (x1, y1) {
+        let doubled_error = 2 * error;
+        if doubled_error >= dy {
+            error += dy;
+            x += sx;
+        }
+        if doubled_error <= dx {
+            error += dx;
+            y += sy;
+        }
+        points.push((x, y));
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let palette = Palette::new(&[('#', FOREGROUND)], BACKGROUND);
+        let mut picture = Picture::from_grid("#..\n.#.", &palette);
+        picture.fill([(1, 2), (5, 5)], HIGHLIGHT);
+        picture.outline(vec![(0.0, 0.0), (1.0, 2.0)], RED);
+
+        let ppm = picture.to_ppm(2);
+        let header = b"P6\n6 4\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 6 * 4 * 3);
+        let pixel = |x: usize, y: usize| &ppm[header.len() + (y * 6 + x) * 3..][..3];
+        assert_eq!(pixel(0, 0), [204, 204, 204]);
+        assert_eq!(pixel(2, 0), [15, 15, 35]);
+        // The outline starts at the center of the first cell
+        assert_eq!(pixel(1, 1), [230, 60, 60]);
+        assert_eq!(pixel(5, 2), [255, 255, 102]);
+
+        let svg = picture.to_svg(2);
+        assert!(svg.contains("<rect x=\"1\" y=\"0\" width=\"2\" height=\"1\" fill=\"#0f0f23\"/>"));
+        assert!(svg.contains("<rect x=\"2\" y=\"1\" width=\"1\" height=\"1\" fill=\"#ffff66\"/>"));
+        assert!(svg.contains("points=\"0.5,0.5 2.5,1.5\""));
+
+        let ((width, height), points) = fit(&[(0, 0), (-1000, 500)], 11);
+        assert_eq!((width, height), (6, 11));
+        assert_eq!(points, vec![(10.0, 0.0), (0.0, 5.0)]);
+    }
+}
@@ -0,0 +1,163 @@
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::calendar::{format_duration, unlock_time};
+use crate::client::Client;
+use crate::terminal::CLEAR_LINE;
+
+/// How many times to try fetching an input once its puzzle unlocks, since the
+/// input can take a moment to become available.
+const FETCH_ATTEMPTS: u32 = 5;
+const FETCH_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Where the current time comes from, so that waiting can be tested without
+/// any actual waiting.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The system's clock.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Waits for a day's puzzle to unlock, counting down in the terminal, then
+/// fetches its input and runs the day's tests. Returns whether the tests
+/// passed.
+pub fn wait(client: &Client, year: u16, day: u8, clock: &impl Clock) -> io::Result<bool> {
+    count_down(day, unlock_time(year, day), clock, &mut io::stdout())?;
+
+    let mut attempt = 1;
+    let path = loop {
+        match client.fetch_input(day) {
+            Ok(path) => break path,
+            Err(error) if attempt < FETCH_ATTEMPTS => {
+                println!("Failed to fetch input ({error}); retrying...");
+                clock.sleep(FETCH_RETRY_DELAY);
+                attempt += 1;
+            }
+            Err(error) => {
+                return Err(io::Error::new(
+                    error.kind(),
+                    format!("can't fetch input: {error}"),
+                ))
+            }
+        }
+    };
+    println!("Fetched input to {}", path.display());
+
+    run_tests(day)
+}
+
+/// Writes a countdown to `out` until `unlock`, updating it every second.
+fn count_down(
+    day: u8,
+    unlock: SystemTime,
+    clock: &impl Clock,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    while let Ok(remaining) = unlock.duration_since(clock.now()) {
+        if remaining.is_zero() {
+            break;
+        }
+        write!(
+            out,
+            "\rDay {day} unlocks in {}{CLEAR_LINE}",
+            format_duration(remaining)
+        )?;
+        out.flush()?;
+
+        // Waking on whole seconds keeps the countdown ticking evenly
+        let fraction = Duration::from_nanos(remaining.subsec_nanos().into());
+        clock.sleep(if fraction.is_zero() {
+            Duration::from_secs(1)
+        } else {
+            fraction
+        });
+    }
+    writeln!(out, "\rDay {day} is unlocked!{CLEAR_LINE}")
+}
+
+/// Runs a day's tests in a separate `cargo test` process, like `watch` does.
+fn run_tests(day: u8) -> io::Result<bool> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    if !root.join(format!("src/day{day}.rs")).exists() {
+        println!("Day {day} has no solution yet; start one with `aoc new --day {day}`");
+        return Ok(true);
+    }
+
+    println!("Running tests for day {day}...");
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let status = Command::new(cargo)
+        .arg("test")
+        .arg("--quiet")
+        .arg("--manifest-path")
+        .arg(root.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(root.join("target/watch"))
+        .arg("--lib")
+        .arg(format!("day{day}::"))
+        .status()
+        .map_err(|error| io::Error::other(format!("failed to run cargo: {error}")))?;
+    Ok(status.success())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    struct FakeClock {
+        now: Cell<SystemTime>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> SystemTime {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.now.set(self.now.get() + duration);
+            self.sleeps.borrow_mut().push(duration);
+        }
+    }
+
+    fn utc(seconds: u64) -> SystemTime {
+        std::time::UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn it_works() {
+        let clock = FakeClock {
+            now: Cell::new(utc(1_701_406_800) - Duration::from_millis(2500)),
+            sleeps: RefCell::new(vec![]),
+        };
+        let mut out = vec![];
+        count_down(1, unlock_time(2023, 1), &clock, &mut out).unwrap();
+        assert_eq!(clock.now(), unlock_time(2023, 1));
+        assert_eq!(
+            *clock.sleeps.borrow(),
+            [500, 1000, 1000].map(Duration::from_millis)
+        );
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\rDay 1 unlocks in 00:00:03\x1b[K\
+             \rDay 1 unlocks in 00:00:02\x1b[K\
+             \rDay 1 unlocks in 00:00:01\x1b[K\
+             \rDay 1 is unlocked!\x1b[K\n"
+        );
+    }
+}
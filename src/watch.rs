@@ -0,0 +1,185 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::input;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, PartialEq)]
+struct Answer {
+    part: u8,
+    duration: String,
+    value: String,
+    counters: Option<String>,
+}
+
+/// Polls a day's source and input files, re-running the day whenever either
+/// of them changes. Each run happens in a separate `cargo run` process so
+/// that source changes get recompiled. The run reads the same config file
+/// as the watcher, given by `config` when it was passed on the command line.
+pub fn watch(day: u8, part: Option<u8>, config: Option<&Path>) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let watched_files = [root.join(format!("src/day{day}.rs")), input::path(day)];
+
+    println!("Watching day {day} for changes (Ctrl-C to stop)...");
+
+    let mut last_modified = None;
+    let mut previous_answers = vec![];
+
+    loop {
+        let modified: Vec<_> = watched_files
+            .iter()
+            .map(|path| modified_time(path))
+            .collect();
+        if last_modified.as_ref() != Some(&modified) {
+            last_modified = Some(modified);
+
+            println!("\nRunning solution for day {day}...");
+            if let Some(answers) = run(root, day, part, config) {
+                report(&previous_answers, &answers);
+                previous_answers = answers;
+            }
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    path.metadata()
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+fn run(root: &Path, day: u8, part: Option<u8>, config: Option<&Path>) -> Option<Vec<Answer>> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+
+    // A separate target directory keeps the child build from replacing the
+    // binary that is currently running the watcher.
+    let mut command = Command::new(cargo);
+    command
+        .arg("run")
+        .arg("--quiet")
+        .arg("--manifest-path")
+        .arg(root.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(root.join("target/watch"))
+        .arg("--")
+        .arg("--input-dir")
+        .arg(input::dir())
+        // The answers are read from the text output, whatever format the
+        // config asks for
+        .arg("--format")
+        .arg("text")
+        .arg("run")
+        .arg("--no-cache")
+        .arg("--day")
+        .arg(day.to_string());
+    if let Some(config) = config {
+        command.arg("--config").arg(config);
+    }
+    if let Some(part) = part {
+        command.arg("--part").arg(part.to_string());
+    }
+
+    let output = match command.stderr(Stdio::inherit()).output() {
+        Ok(output) => output,
+        Err(error) => {
+            println!("Failed to run cargo: {error}");
+            return None;
+        }
+    };
+
+    if !output.status.success() {
+        // A failed part exits with an error too, with its reason in the output
+        print!("{}", String::from_utf8_lossy(&output.stdout));
+        println!("Run failed ({}); waiting for changes...", output.status);
+        return None;
+    }
+
+    Some(parse_answers(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn parse_answers(output: &str) -> Vec<Answer> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let rest = line.strip_prefix("Part ")?;
+            let (part, rest) = rest.split_once(" (")?;
+            let (duration, rest) = rest.split_once("): ")?;
+            let (value, counters) = match rest.split_once(" [") {
+                Some((value, counters)) => (value, counters.strip_suffix(']')),
+                None => (rest, None),
+            };
+            Some(Answer {
+                part: part.parse().ok()?,
+                duration: duration.to_string(),
+                value: value.to_string(),
+                counters: counters.map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+fn report(previous_answers: &[Answer], answers: &[Answer]) {
+    for answer in answers {
+        let previous = previous_answers.iter().find(|a| a.part == answer.part);
+        let change = match previous {
+            None => String::new(),
+            Some(previous) if previous.value == answer.value => " (unchanged)".to_string(),
+            Some(previous) => format!(" (changed from {})", previous.value),
+        };
+        let counters = match (&answer.counters, previous) {
+            (
+                Some(counters),
+                Some(Answer {
+                    counters: Some(previous_counters),
+                    ..
+                }),
+            ) if counters != previous_counters => {
+                format!(" [{counters}] (was [{previous_counters}])")
+            }
+            (Some(counters), _) => format!(" [{counters}]"),
+            (None, _) => String::new(),
+        };
+        println!(
+            "Part {} ({}): {}{}{}",
+            answer.part, answer.duration, answer.value, change, counters
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_answers() {
+        let output = "
+Running solution for day 3...
+Part 1 (1.2ms): 4361
+Part 2 (980µs): 467835 [memo hits: 12, nodes popped: 3]
+
+1.5ms elapsed.";
+
+        assert_eq!(
+            parse_answers(output),
+            vec![
+                Answer {
+                    part: 1,
+                    duration: "1.2ms".to_string(),
+                    value: "4361".to_string(),
+                    counters: None,
+                },
+                Answer {
+                    part: 2,
+                    duration: "980µs".to_string(),
+                    value: "467835".to_string(),
+                    counters: Some("memo hits: 12, nodes popped: 3".to_string()),
+                },
+            ]
+        );
+    }
+}